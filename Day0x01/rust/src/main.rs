@@ -1,5 +1,5 @@
 use lazy_static::lazy_static;
-use std::{collections::HashMap, fs};
+use std::{env, fs};
 
 fn read_file(filename: &str) -> String {
     let file_contents: String =
@@ -8,19 +8,47 @@ fn read_file(filename: &str) -> String {
     return file_contents;
 }
 
-fn calibrate_digits(line: &str) -> Result<u32, &str> {
+// DIGIT ZERO of every decimal digit (Nd) range in Unicode 17.0's UnicodeData.txt;
+// each range holds the digits zero to nine at consecutive code points.
+const UNICODE_DIGIT_ZEROS: [u32; 77] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450,
+    0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50,
+    0x11D50, 0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0,
+    0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950,
+    0x1FBF0,
+];
+
+fn parse_digit(c: char, unicode: bool) -> Option<u32> {
+    if !unicode {
+        return c.to_digit(10);
+    }
+
+    let code_point: u32 = c as u32;
+    for &zero in UNICODE_DIGIT_ZEROS.iter() {
+        if code_point >= zero && code_point < zero + 10 {
+            return Some(code_point - zero);
+        }
+    }
+
+    return None;
+}
+
+fn calibrate_digits(line: &str, unicode: bool) -> Result<u32, &str> {
     let mut left_digit: Option<u32> = None;
     for c in line.chars() {
-        if c.is_digit(10) {
-            left_digit = c.to_digit(10);
+        if let Some(d) = parse_digit(c, unicode) {
+            left_digit = Some(d);
             break;
         }
     }
 
     let mut right_digit: Option<u32> = None;
     for c in line.chars().rev() {
-        if c.is_digit(10) {
-            right_digit = c.to_digit(10);
+        if let Some(d) = parse_digit(c, unicode) {
+            right_digit = Some(d);
             break;
         }
     }
@@ -33,37 +61,36 @@ fn calibrate_digits(line: &str) -> Result<u32, &str> {
     return cal_val;
 }
 
-fn find_word(line: &str, words: &Vec<&str>, reverse: bool) -> Option<usize> {
-    let mut first_char_idx: Option<usize> = None;
-    let mut first_word_idx: Option<usize> = None;
+fn find_number(line: &str, words: &Vec<(&str, u32)>, unicode: bool, reverse: bool) -> Option<u32> {
+    let char_indices: Vec<(usize, char)> = line.char_indices().collect();
+    let n_chars: usize = char_indices.len();
 
-    for (word_idx, word) in words.iter().enumerate() {
-        let search: Option<usize>;
-        if reverse {
-            search = line.rfind(word)
+    for i in 0..n_chars {
+        let (byte_idx, c) = if reverse {
+            char_indices[n_chars - 1 - i]
         } else {
-            search = line.find(word)
+            char_indices[i]
+        };
+
+        if let Some(d) = parse_digit(c, unicode) {
+            if d > 0 {
+                return Some(d);
+            }
         }
 
-        if let Some(char_idx) = search {
-            if let Some(curr_char_idx) = first_char_idx {
-                if (reverse && char_idx > curr_char_idx) || (!reverse && char_idx < curr_char_idx) {
-                    first_char_idx = Some(char_idx);
-                    first_word_idx = Some(word_idx);
-                }
-            } else {
-                first_char_idx = Some(char_idx);
-                first_word_idx = Some(word_idx);
+        for &(word, num) in words.iter() {
+            if line[byte_idx..].starts_with(word) {
+                return Some(num);
             }
         }
     }
 
-    return first_word_idx;
+    return None;
 }
 
-fn calibrate_words(line: &str) -> Result<u32, &str> {
+fn calibrate_words(line: &str, unicode: bool) -> Result<u32, &str> {
     lazy_static! {
-        static ref NUM_MAP: HashMap<&'static str, u32> = HashMap::from([
+        static ref NUM_WORDS: Vec<(&'static str, u32)> = Vec::from([
             ("one", 1),
             ("two", 2),
             ("three", 3),
@@ -73,35 +100,49 @@ fn calibrate_words(line: &str) -> Result<u32, &str> {
             ("seven", 7),
             ("eight", 8),
             ("nine", 9),
-            ("1", 1),
-            ("2", 2),
-            ("3", 3),
-            ("4", 4),
-            ("5", 5),
-            ("6", 6),
-            ("7", 7),
-            ("8", 8),
-            ("9", 9),
         ]);
+        static ref UNICODE_NUM_WORDS: Vec<(&'static str, u32)> = Vec::from([
+            ("一", 1),
+            ("二", 2),
+            ("三", 3),
+            ("四", 4),
+            ("五", 5),
+            ("六", 6),
+            ("七", 7),
+            ("八", 8),
+            ("九", 9),
+            ("один", 1),
+            ("два", 2),
+            ("три", 3),
+            ("четыре", 4),
+            ("пять", 5),
+            ("шесть", 6),
+            ("семь", 7),
+            ("восемь", 8),
+            ("девять", 9),
+            ("ένα", 1),
+            ("δύο", 2),
+            ("τρία", 3),
+            ("τέσσερα", 4),
+            ("πέντε", 5),
+            ("έξι", 6),
+            ("επτά", 7),
+            ("οκτώ", 8),
+            ("εννέα", 9),
+        ]);
+        static ref ALL_NUM_WORDS: Vec<(&'static str, u32)> =
+            [NUM_WORDS.as_slice(), UNICODE_NUM_WORDS.as_slice()].concat();
     }
 
-    let mut num_keys = Vec::new();
-    for k in NUM_MAP.keys() {
-        num_keys.push(*k);
-    }
+    let num_words: &Vec<(&str, u32)> = if unicode { &ALL_NUM_WORDS } else { &NUM_WORDS };
 
     let mut cal_val: Result<u32, &str> = Err("Failed to calibrate");
 
-    if let (Some(left_word_idx), Some(right_word_idx)) = (
-        find_word(line, &num_keys, false),
-        find_word(line, &num_keys, true),
+    if let (Some(l), Some(r)) = (
+        find_number(line, num_words, unicode, false),
+        find_number(line, num_words, unicode, true),
     ) {
-        if let (Some(l), Some(r)) = (
-            NUM_MAP.get(num_keys[left_word_idx]),
-            NUM_MAP.get(num_keys[right_word_idx]),
-        ) {
-            cal_val = Ok(((*l) * 10) + (*r));
-        }
+        cal_val = Ok((l * 10) + r);
     }
 
     return cal_val;
@@ -110,15 +151,16 @@ fn calibrate_words(line: &str) -> Result<u32, &str> {
 fn main() {
     let filename: &str = "../document.txt";
     let file_contents: String = read_file(filename);
+    let unicode: bool = env::args().any(|arg| arg == "--unicode");
 
     let mut cal_digits_sum: u32 = 0;
     let mut cal_words_sum: u32 = 0;
     for line in file_contents.lines() {
-        if let Ok(val) = calibrate_digits(line) {
+        if let Ok(val) = calibrate_digits(line, unicode) {
             cal_digits_sum += val;
         }
 
-        if let Ok(val) = calibrate_words(line) {
+        if let Ok(val) = calibrate_words(line, unicode) {
             cal_words_sum += val;
         }
     }
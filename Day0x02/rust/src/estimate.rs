@@ -0,0 +1,136 @@
+use crate::{Bag, Game};
+use std::{
+    cmp::{max, min},
+    collections::BTreeMap,
+    fmt,
+};
+
+const Z_95: f64 = 1.96;
+
+pub struct BagEstimate {
+    pub bag: Bag,
+    pub log_likelihood: f64,
+    pub bounds: BTreeMap<String, (u32, u32)>,
+}
+
+impl fmt::Display for BagEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bound_strs: Vec<String> = self
+            .bounds
+            .iter()
+            .map(|(color, (low, high))| format!("{}={}..{}", color, low, high))
+            .collect();
+
+        return write!(
+            f,
+            "{} (95% CI {}, log-likelihood {:.3})",
+            self.bag,
+            bound_strs.join(","),
+            self.log_likelihood
+        );
+    }
+}
+
+fn wilson_interval(successes: u32, trials: u32, z: f64) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+
+    let n: f64 = trials as f64;
+    let p: f64 = (successes as f64) / n;
+    let z_sq: f64 = z * z;
+
+    let denominator: f64 = 1.0 + (z_sq / n);
+    let center: f64 = (p + (z_sq / (2.0 * n))) / denominator;
+    let half_width: f64 =
+        (z / denominator) * f64::sqrt(((p * (1.0 - p)) / n) + (z_sq / (4.0 * n * n)));
+
+    return (
+        f64::max(0.0, center - half_width),
+        f64::min(1.0, center + half_width),
+    );
+}
+
+// Each cube in a draw is treated as an independent sample, with replacement,
+// from a bag holding `counts[color]` cubes of each color, so the likelihood of
+// the observed draws only depends on the color proportions. Every bag must
+// also be able to hold its largest single draw of each color, so the search
+// starts at the minimum bag and grows one cube at a time up to `max_total`,
+// keeping the most likely bag seen. Colors only ever drawn zero times add
+// nothing to the likelihood, since 0 * ln(0) is taken as 0.
+pub fn estimate_bag(games: &[&Game], max_total: u32) -> BagEstimate {
+    let mut observed: BTreeMap<String, u32> = BTreeMap::new();
+    let mut bag = Bag::new();
+    for game in games.iter() {
+        for draw in game.draws.iter() {
+            for (color, &count) in draw.cubes.iter() {
+                *observed.entry(color.clone()).or_insert(0) += count;
+                let fewest_count: u32 = bag.get(color);
+                bag.counts.insert(color.clone(), max(fewest_count, count));
+            }
+        }
+    }
+
+    let n_observed: u32 = observed.values().sum();
+    let log_likelihood = |bag: &Bag| -> f64 {
+        let total: f64 = bag.total() as f64;
+        return observed
+            .iter()
+            .filter(|(_, &count)| count > 0)
+            .map(|(color, &count)| (count as f64) * f64::ln((bag.get(color) as f64) / total))
+            .sum();
+    };
+
+    let minimum_bag: Bag = bag.clone();
+    let mut best_bag: Bag = bag.clone();
+    let mut best_log_likelihood: f64 = log_likelihood(&bag);
+    for _ in bag.total()..max_total {
+        let mut best_color: Option<&String> = None;
+        let mut best_gain: f64 = f64::NEG_INFINITY;
+        for (color, &count) in observed.iter().filter(|(_, &count)| count > 0) {
+            let curr_count: f64 = bag.get(color) as f64;
+            let gain: f64 = (count as f64) * (f64::ln(curr_count + 1.0) - f64::ln(curr_count));
+            if gain > best_gain {
+                best_color = Some(color);
+                best_gain = gain;
+            }
+        }
+
+        let color: &String = match best_color {
+            Some(c) => c,
+            None => break,
+        };
+        let curr_count: u32 = bag.get(color);
+        bag.counts.insert(color.clone(), curr_count + 1);
+
+        let curr_log_likelihood: f64 = log_likelihood(&bag);
+        if curr_log_likelihood > best_log_likelihood + 1e-12 {
+            best_bag = bag.clone();
+            best_log_likelihood = curr_log_likelihood;
+        }
+    }
+
+    // The interval on each color's proportion is scaled by the estimated bag
+    // size and widened where needed so it always contains the estimate.
+    let best_total: f64 = best_bag.total() as f64;
+    let mut bounds: BTreeMap<String, (u32, u32)> = BTreeMap::new();
+    for (color, &count) in observed.iter() {
+        let (low, high) = wilson_interval(count, n_observed, Z_95);
+        let fewest_count: u32 = minimum_bag.get(color);
+        let best_count: u32 = best_bag.get(color);
+        let low_count: u32 = max(
+            min(f64::floor(low * best_total) as u32, best_count),
+            fewest_count,
+        );
+        let high_count: u32 = max(f64::ceil(high * best_total) as u32, best_count);
+        bounds.insert(color.clone(), (low_count, high_count));
+    }
+
+    let estimate = BagEstimate {
+        bag: best_bag,
+        log_likelihood: best_log_likelihood,
+        bounds: bounds,
+    };
+
+    return estimate;
+}
@@ -0,0 +1,73 @@
+use crate::{Draw, Game};
+use std::collections::BTreeMap;
+
+const CSV_HEADER: &str = "game_id,draw_index,colour,count";
+
+pub fn to_csv(games: &[Game]) -> String {
+    let mut csv: String = String::from(CSV_HEADER);
+    csv.push('\n');
+    for game in games.iter() {
+        for (draw_idx, draw) in game.draws.iter().enumerate() {
+            for (color, count) in draw.cubes.iter() {
+                csv.push_str(&format!("{},{},{},{}\n", game.id, draw_idx, color, count));
+            }
+        }
+    }
+
+    return csv;
+}
+
+pub fn from_csv(s: &str) -> Vec<Game> {
+    let mut games: Vec<Game> = Vec::new();
+    for (line_idx, line) in s.lines().enumerate() {
+        if line.trim().is_empty() || (line_idx == 0 && line.trim() == CSV_HEADER) {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split::<char>(',').map(|f| f.trim()).collect();
+        if fields.len() != 4 {
+            panic!("CSV record should have exactly 4 fields");
+        }
+
+        let game_id: u32 = fields[0]
+            .parse::<u32>()
+            .expect("Game ID should be positive integer");
+        let draw_idx: usize = fields[1]
+            .parse::<usize>()
+            .expect("Draw index should be non-negative integer");
+        let color: &str = fields[2];
+        let count: u32 = fields[3]
+            .parse::<u32>()
+            .expect("Cube count should be positive integer");
+
+        let game_idx: usize = match games.iter().position(|g| g.id == game_id) {
+            Some(idx) => idx,
+            None => {
+                games.push(Game {
+                    id: game_id,
+                    draws: Vec::new(),
+                });
+                games.len() - 1
+            }
+        };
+
+        let draws: &mut Vec<Draw> = &mut games[game_idx].draws;
+        while draws.len() <= draw_idx {
+            draws.push(Draw {
+                cubes: BTreeMap::new(),
+            });
+        }
+
+        *draws[draw_idx].cubes.entry(color.to_string()).or_insert(0) += count;
+    }
+
+    return games;
+}
+
+pub fn to_json(games: &[Game]) -> String {
+    return serde_json::to_string_pretty(games).expect("Should be able to serialize games");
+}
+
+pub fn from_json(s: &str) -> Vec<Game> {
+    return serde_json::from_str::<Vec<Game>>(s).expect("Should be able to deserialize games");
+}
@@ -1,10 +1,11 @@
+mod estimate;
+mod export;
+mod query;
+
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::{cmp::max, collections::HashMap, fs, str::Split};
-
-const RED: &str = "red";
-const GREEN: &str = "green";
-const BLUE: &str = "blue";
+use serde::{Deserialize, Serialize};
+use std::{cmp::max, collections::BTreeMap, env, fmt, fs, str::Split};
 
 fn read_file(filename: &str) -> String {
     let file_contents: String =
@@ -13,7 +14,95 @@ fn read_file(filename: &str) -> String {
     return file_contents;
 }
 
-fn game_possibility(line: &str, max_red: u32, max_green: u32, max_blue: u32) -> u32 {
+#[derive(Clone)]
+struct Bag {
+    counts: BTreeMap<String, u32>,
+}
+
+impl Bag {
+    fn new() -> Bag {
+        return Bag {
+            counts: BTreeMap::new(),
+        };
+    }
+
+    fn from(counts: &[(&str, u32)]) -> Bag {
+        let mut bag = Bag::new();
+        for &(color, count) in counts {
+            bag.counts.insert(color.to_string(), count);
+        }
+
+        return bag;
+    }
+
+    fn get(&self, color: &str) -> u32 {
+        if let Some(&count) = self.counts.get(color) {
+            return count;
+        }
+
+        return 0;
+    }
+
+    fn power(&self) -> u32 {
+        return self.counts.values().product();
+    }
+
+    fn total(&self) -> u32 {
+        return self.counts.values().sum();
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cube_strs: Vec<String> = self
+            .counts
+            .iter()
+            .map(|(color, count)| format!("{}={}", color, count))
+            .collect();
+
+        return write!(f, "{}", cube_strs.join(","));
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct Draw {
+    cubes: BTreeMap<String, u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Game {
+    id: u32,
+    draws: Vec<Draw>,
+}
+
+impl Game {
+    fn is_possible(&self, bag: &Bag) -> bool {
+        for draw in self.draws.iter() {
+            for (color, &count) in draw.cubes.iter() {
+                if count > bag.get(color) {
+                    return false;
+                }
+            }
+        }
+
+        return true;
+    }
+
+    fn minimum_bag(&self) -> Bag {
+        let mut bag = Bag::new();
+        for draw in self.draws.iter() {
+            for (color, &count) in draw.cubes.iter() {
+                let fewest_count: u32 = bag.get(color);
+                bag.counts.insert(color.clone(), max(fewest_count, count));
+            }
+        }
+
+        return bag;
+    }
+}
+
+fn parse_game(line: &str) -> Game {
     lazy_static! {
         static ref RE_LINE: Regex =
             Regex::new(r"^Game (\d+):\s*(.*)\s*$").expect("Should be able to parse line");
@@ -21,9 +110,6 @@ fn game_possibility(line: &str, max_red: u32, max_green: u32, max_blue: u32) ->
             .expect("Should be able to parse cube count and color");
     }
 
-    let max_count_map: HashMap<&str, u32> =
-        HashMap::from([(RED, max_red), (GREEN, max_green), (BLUE, max_blue)]);
-
     let line_caps = RE_LINE
         .captures(line)
         .expect("Should be able to parse line");
@@ -38,8 +124,13 @@ fn game_possibility(line: &str, max_red: u32, max_green: u32, max_blue: u32) ->
         .expect("Should be able to parse cubes data")
         .as_str();
 
+    let mut draws: Vec<Draw> = Vec::new();
     let draw_strs: Split<char> = cubes_str.split::<char>(';');
     for draw_str in draw_strs {
+        let mut draw = Draw {
+            cubes: BTreeMap::new(),
+        };
+
         let cube_strs: Split<char> = draw_str.split::<char>(',');
         for cube_str in cube_strs {
             let cube_caps = RE_CUBE
@@ -56,90 +147,140 @@ fn game_possibility(line: &str, max_red: u32, max_green: u32, max_blue: u32) ->
                 .expect("Should be able to parse cube color")
                 .as_str();
 
-            let &max_cube_count: &u32 = max_count_map
-                .get(cube_color)
-                .expect("Should be able to look up cube color");
-            if cube_count > max_cube_count {
-                return 0;
-            }
+            *draw.cubes.entry(cube_color.to_string()).or_insert(0) += cube_count;
         }
+
+        draws.push(draw);
     }
 
-    return game_id;
+    let game = Game {
+        id: game_id,
+        draws: draws,
+    };
+
+    return game;
 }
 
-fn game_power(line: &str) -> u32 {
-    lazy_static! {
-        static ref RE_LINE: Regex =
-            Regex::new(r"^Game \d+:\s*(.*)\s*$").expect("Should be able to parse line");
-        static ref RE_CUBE: Regex = Regex::new(r"^\s*(\d+)\s+(\S+)\s*$")
-            .expect("Should be able to parse cube count and color");
+fn parse_bag(s: &str) -> Bag {
+    let mut bag = Bag::new();
+    for cube_str in s.split::<char>(',') {
+        let (color, count_str) = cube_str
+            .split_once('=')
+            .expect("Bag should be given as color=count pairs");
+        let count: u32 = count_str
+            .trim()
+            .parse::<u32>()
+            .expect("Cube count should be positive integer");
+        bag.counts.insert(color.trim().to_string(), count);
     }
 
-    let mut fewest_counts_map: HashMap<&str, u32> =
-        HashMap::from([(RED, 0), (GREEN, 0), (BLUE, 0)]);
-
-    let line_caps = RE_LINE
-        .captures(line)
-        .expect("Should be able to parse line");
-    let cubes_str: &str = line_caps
-        .get(1)
-        .expect("Should be able to parse cubes data")
-        .as_str();
+    return bag;
+}
 
-    let draw_strs: Split<char> = cubes_str.split::<char>(';');
-    for draw_str in draw_strs {
-        let cube_strs: Split<char> = draw_str.split::<char>(',');
-        for cube_str in cube_strs {
-            let cube_caps = RE_CUBE
-                .captures(cube_str)
-                .expect("Should be able to parse cube data");
-            let cube_count: u32 = cube_caps
-                .get(1)
-                .expect("Should be able to cube count")
-                .as_str()
+fn parse_game_ids(s: &str) -> Vec<u32> {
+    return s
+        .split::<char>(',')
+        .map(|id_str| {
+            id_str
+                .trim()
                 .parse::<u32>()
-                .expect("Cube count should be positive integer");
-            let cube_color: &str = cube_caps
-                .get(2)
-                .expect("Should be able to parse cube color")
-                .as_str();
+                .expect("Game ID should be positive integer")
+        })
+        .collect();
+}
 
-            let &fewest_cube_count: &u32 = fewest_counts_map
-                .get(cube_color)
-                .expect("Should be able to look up cube color");
-            fewest_counts_map.insert(cube_color, max(cube_count, fewest_cube_count));
-        }
+fn game_possibility(game: &Game, bag: &Bag) -> u32 {
+    if game.is_possible(bag) {
+        return game.id;
     }
 
-    let &fewest_red_count: &u32 = fewest_counts_map
-        .get(RED)
-        .expect("Should be able to get red count");
-    let &fewest_green_count: &u32 = fewest_counts_map
-        .get(GREEN)
-        .expect("Should be able to get red count");
-    let &fewest_blue_count: &u32 = fewest_counts_map
-        .get(BLUE)
-        .expect("Should be able to get red count");
+    return 0;
+}
 
-    return fewest_red_count * fewest_green_count * fewest_blue_count;
+fn game_power(game: &Game) -> u32 {
+    return game.minimum_bag().power();
 }
 
-fn main() {
-    let filename: &str = "../games.txt";
-    let file_contents: String = read_file(filename);
+fn parse_games(filename: &str, file_contents: &str) -> Vec<Game> {
+    if filename.ends_with(".csv") {
+        return export::from_csv(file_contents);
+    }
+
+    if filename.ends_with(".json") {
+        return export::from_json(file_contents);
+    }
+
     let lines: Split<char> = file_contents.split::<char>('\n');
+    let games: Vec<Game> = lines.map(parse_game).collect();
+
+    return games;
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let mut filename: String = String::from("../games.txt");
+    if args.len() > 1 && args[0] == "--games" {
+        filename = args[1].clone();
+        args.drain(0..2);
+    }
+
+    let file_contents: String = read_file(&filename);
+    let games: Vec<Game> = parse_games(&filename, &file_contents);
+
+    if !args.is_empty() {
+        let query_arg: &str = args.get(1).expect("Query should have an argument");
+        match args[0].as_str() {
+            "--possible" => {
+                let bag: Bag = parse_bag(query_arg);
+                for game in query::possible_games(&games, &bag) {
+                    println!("{}", game.id);
+                }
+            }
+            "--smallest-bag" => {
+                let game_ids: Vec<u32> = parse_game_ids(query_arg);
+                let bag: Bag = query::smallest_bag(&games, &game_ids);
+                println!("{} ({} cubes)", bag, bag.total());
+            }
+            "--deficit" => {
+                let bag: Bag = parse_bag(query_arg);
+                for game in games.iter() {
+                    println!("{}: {}", game.id, query::bag_deficit(game, &bag));
+                }
+            }
+            "--estimate" => {
+                let max_total: u32 = query_arg
+                    .parse::<u32>()
+                    .expect("Maximum bag size should be positive integer");
+                for game in games.iter() {
+                    let estimate = estimate::estimate_bag(&[game], max_total);
+                    println!("{}: {}", game.id, estimate);
+                }
+
+                let all_games: Vec<&Game> = games.iter().collect();
+                let estimate = estimate::estimate_bag(&all_games, max_total);
+                println!("all: {}", estimate);
+            }
+            "--export" => match query_arg {
+                "csv" => print!("{}", export::to_csv(&games)),
+                "json" => println!("{}", export::to_json(&games)),
+                _ => panic!("Export format should be csv or json"),
+            },
+            _ => panic!(
+                "Query should be one of --possible, --smallest-bag, --deficit, --estimate or --export"
+            ),
+        }
+
+        return;
+    }
 
-    let max_red: u32 = 12;
-    let max_green: u32 = 13;
-    let max_blue: u32 = 14;
+    let bag = Bag::from(&[("red", 12), ("green", 13), ("blue", 14)]);
 
     let mut sum_ids: u32 = 0;
     let mut sum_powers: u32 = 0;
 
-    for line in lines {
-        sum_ids += game_possibility(line, max_red, max_green, max_blue);
-        sum_powers += game_power(line);
+    for game in games.iter() {
+        sum_ids += game_possibility(game, &bag);
+        sum_powers += game_power(game);
     }
 
     println!("{}", sum_ids);
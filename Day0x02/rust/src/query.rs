@@ -0,0 +1,44 @@
+use crate::{Bag, Game};
+use std::cmp::max;
+
+pub fn possible_games<'a>(games: &'a [Game], bag: &Bag) -> Vec<&'a Game> {
+    let mut possible: Vec<&Game> = Vec::new();
+    for game in games.iter() {
+        if game.is_possible(bag) {
+            possible.push(game);
+        }
+    }
+
+    return possible;
+}
+
+pub fn smallest_bag(games: &[Game], game_ids: &[u32]) -> Bag {
+    let mut bag = Bag::new();
+    for &game_id in game_ids.iter() {
+        let game: &Game = games
+            .iter()
+            .find(|g| g.id == game_id)
+            .expect("Should be able to look up game");
+
+        for (color, &count) in game.minimum_bag().counts.iter() {
+            let smallest_count: u32 = bag.get(color);
+            bag.counts.insert(color.clone(), max(smallest_count, count));
+        }
+    }
+
+    return bag;
+}
+
+pub fn bag_deficit(game: &Game, bag: &Bag) -> Bag {
+    let mut deficit = Bag::new();
+    for (color, &count) in game.minimum_bag().counts.iter() {
+        let available_count: u32 = bag.get(color);
+        if count > available_count {
+            deficit
+                .counts
+                .insert(color.clone(), count - available_count);
+        }
+    }
+
+    return deficit;
+}
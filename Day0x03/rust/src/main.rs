@@ -1,4 +1,6 @@
-use std::{collections::HashMap, fs, str::Split};
+mod render;
+
+use std::{collections::HashMap, env, fmt, fs, str::Lines};
 
 fn read_file(filename: &str) -> String {
     let file_contents: String =
@@ -11,50 +13,134 @@ struct EngineNumber {
     row_idx: usize,
     start_col_idx: usize,
     end_col_idx: usize,
-    num: u32,
+    num: u64,
 }
 
 struct EngineGear {
-    n_part_nums: usize,
-    ratio: u32,
+    sym: char,
+    part_nums: Vec<u64>,
+}
+
+enum GearArity {
+    Exact(usize),
+    Min(usize),
+    Max(usize),
+}
+
+enum GearAggregation {
+    Product,
+    Sum,
+    Max,
+}
+
+enum AdjacencyMode {
+    FirstMatch,
+    AllMatches,
+}
+
+struct GearRule {
+    symbols: Vec<char>,
+    arity: GearArity,
+    aggregation: GearAggregation,
+}
+
+impl GearRule {
+    fn matches(&self, gear: &EngineGear) -> bool {
+        if !self.symbols.contains(&gear.sym) {
+            return false;
+        }
+
+        let n_part_nums: usize = gear.part_nums.len();
+        let matches_arity: bool = match self.arity {
+            GearArity::Exact(n) => n_part_nums == n,
+            GearArity::Min(n) => n_part_nums >= n,
+            GearArity::Max(n) => n_part_nums <= n,
+        };
+
+        return matches_arity;
+    }
+
+    fn ratio(&self, gear: &EngineGear) -> Option<u64> {
+        let mut part_nums = gear.part_nums.iter();
+        let ratio: Option<u64> = match self.aggregation {
+            GearAggregation::Product => part_nums.try_fold(1, |acc: u64, &n| acc.checked_mul(n)),
+            GearAggregation::Sum => part_nums.try_fold(0, |acc: u64, &n| acc.checked_add(n)),
+            GearAggregation::Max => Some(part_nums.copied().max().unwrap_or(0)),
+        };
+
+        return ratio;
+    }
+}
+
+fn parse_gear_arity(s: &str) -> GearArity {
+    let arity: GearArity = if let Some(n_str) = s.strip_prefix(">=") {
+        GearArity::Min(n_str.parse::<usize>().expect("Arity should be integer"))
+    } else if let Some(n_str) = s.strip_prefix("<=") {
+        GearArity::Max(n_str.parse::<usize>().expect("Arity should be integer"))
+    } else {
+        GearArity::Exact(s.parse::<usize>().expect("Arity should be integer"))
+    };
+
+    return arity;
+}
+
+fn parse_gear_aggregation(s: &str) -> GearAggregation {
+    let aggregation: GearAggregation = match s {
+        "product" => GearAggregation::Product,
+        "sum" => GearAggregation::Sum,
+        "max" => GearAggregation::Max,
+        _ => panic!("Gear aggregation should be one of product, sum or max"),
+    };
+
+    return aggregation;
+}
+
+impl fmt::Display for EngineNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "{} at ({}, {}..{})",
+            self.num, self.row_idx, self.start_col_idx, self.end_col_idx
+        );
+    }
 }
 
 impl EngineNumber {
-    fn surrounding_indices(&self, n_rows: usize, n_cols: usize) -> Vec<(usize, usize)> {
+    fn surrounding_indices(&self, row_widths: &[usize]) -> Vec<(usize, usize)> {
         let mut indices: Vec<(usize, usize)> = Vec::new();
+        let mut push_if_in_row = |row_idx: usize, col_idx: usize| {
+            if col_idx < row_widths[row_idx] {
+                indices.push((row_idx, col_idx));
+            }
+        };
+
         if self.start_col_idx > 0 {
-            indices.push((self.row_idx, self.start_col_idx - 1));
+            push_if_in_row(self.row_idx, self.start_col_idx - 1);
         }
 
-        if self.end_col_idx < n_cols - 1 {
-            indices.push((self.row_idx, self.end_col_idx));
-        }
+        push_if_in_row(self.row_idx, self.end_col_idx);
 
         if self.row_idx > 0 {
             if self.start_col_idx > 0 {
-                indices.push((self.row_idx - 1, self.start_col_idx - 1));
+                push_if_in_row(self.row_idx - 1, self.start_col_idx - 1);
             }
 
-            if self.end_col_idx < n_cols - 1 {
-                indices.push((self.row_idx - 1, self.end_col_idx));
-            }
+            push_if_in_row(self.row_idx - 1, self.end_col_idx);
 
             for i in self.start_col_idx..self.end_col_idx {
-                indices.push((self.row_idx - 1, i));
+                push_if_in_row(self.row_idx - 1, i);
             }
         }
 
-        if self.row_idx < n_rows - 1 {
+        if self.row_idx + 1 < row_widths.len() {
             if self.start_col_idx > 0 {
-                indices.push((self.row_idx + 1, self.start_col_idx - 1));
+                push_if_in_row(self.row_idx + 1, self.start_col_idx - 1);
             }
 
-            if self.end_col_idx < n_cols - 1 {
-                indices.push((self.row_idx + 1, self.end_col_idx));
-            }
+            push_if_in_row(self.row_idx + 1, self.end_col_idx);
 
             for i in self.start_col_idx..self.end_col_idx {
-                indices.push((self.row_idx + 1, i));
+                push_if_in_row(self.row_idx + 1, i);
             }
         }
 
@@ -94,7 +180,7 @@ fn parse_engine_numbers(line: &str, row_idx: usize) -> Vec<EngineNumber> {
                 start_col_idx: curr_start_idx,
                 end_col_idx: i,
                 num: curr_str_num
-                    .parse::<u32>()
+                    .parse::<u64>()
                     .expect("Should be able to parse number"),
             });
             continue;
@@ -107,7 +193,7 @@ fn parse_engine_numbers(line: &str, row_idx: usize) -> Vec<EngineNumber> {
             start_col_idx: curr_start_idx,
             end_col_idx: line.chars().count(),
             num: curr_str_num
-                .parse::<u32>()
+                .parse::<u64>()
                 .expect("Should be able to parse number"),
         });
     }
@@ -115,10 +201,14 @@ fn parse_engine_numbers(line: &str, row_idx: usize) -> Vec<EngineNumber> {
     return numbers;
 }
 
+fn is_padding(c: char) -> bool {
+    return c == '.' || c.is_whitespace();
+}
+
 fn parse_engine_symbols(line: &str, row_idx: usize) -> HashMap<(usize, usize), char> {
     let mut symbols: HashMap<(usize, usize), char> = HashMap::new();
     for (i, c) in line.chars().enumerate() {
-        if !c.is_digit(10) && c != '.' {
+        if !c.is_digit(10) && !is_padding(c) {
             symbols.insert((row_idx, i), c);
         }
     }
@@ -126,58 +216,391 @@ fn parse_engine_symbols(line: &str, row_idx: usize) -> HashMap<(usize, usize), c
     return symbols;
 }
 
-fn main() {
-    let filename: &str = "../schematic.txt";
-    let file_contents: String = read_file(filename);
-    let lines: Split<char> = file_contents.split::<char>('\n');
-
-    let mut eng_nums: Vec<EngineNumber> = Vec::new();
-    let mut eng_syms: HashMap<(usize, usize), char> = HashMap::new();
-    let n_rows: usize = lines.clone().count();
-    let mut n_cols: usize = 0;
-    for (i, line) in lines.enumerate() {
-        n_cols = line.chars().count();
-        eng_nums.append(&mut parse_engine_numbers(line, i));
-        eng_syms.extend(parse_engine_symbols(line, i));
-    }
-
-    let mut part_num_sum: u32 = 0;
-    let mut gears: HashMap<(usize, usize), EngineGear> = HashMap::new();
-    for eng_num in eng_nums {
-        let mut is_part_num: bool = false;
-        for surr_indices in eng_num.surrounding_indices(n_rows, n_cols) {
-            if let Some(&sym) = eng_syms.get(&surr_indices) {
-                is_part_num = true;
-                if sym == '*' {
-                    if let Some(gear) = gears.get_mut(&surr_indices) {
-                        gear.n_part_nums += 1;
-                        gear.ratio *= eng_num.num;
-                    } else {
-                        gears.insert(
-                            surr_indices,
-                            EngineGear {
-                                n_part_nums: 1,
-                                ratio: eng_num.num,
-                            },
-                        );
-                    }
+struct Schematic {
+    rows: Vec<Vec<char>>,
+    numbers: Vec<EngineNumber>,
+    symbols: HashMap<(usize, usize), char>,
+    row_widths: Vec<usize>,
+}
+
+impl Schematic {
+    fn parse(s: &str) -> Schematic {
+        let lines: Lines = s.lines();
+
+        let mut rows: Vec<Vec<char>> = Vec::new();
+        let mut numbers: Vec<EngineNumber> = Vec::new();
+        let mut symbols: HashMap<(usize, usize), char> = HashMap::new();
+        let mut row_widths: Vec<usize> = Vec::new();
+        for (i, line) in lines.enumerate() {
+            row_widths.push(line.chars().count());
+            rows.push(line.chars().collect());
+            numbers.append(&mut parse_engine_numbers(line, i));
+            symbols.extend(parse_engine_symbols(line, i));
+        }
+
+        let schematic = Schematic {
+            rows: rows,
+            numbers: numbers,
+            symbols: symbols,
+            row_widths: row_widths,
+        };
+
+        return schematic;
+    }
+
+    fn number_at(&self, cell: (usize, usize)) -> Option<&EngineNumber> {
+        return self
+            .numbers
+            .iter()
+            .find(|n| n.row_idx == cell.0 && n.start_col_idx <= cell.1 && cell.1 < n.end_col_idx);
+    }
+
+    fn numbers_adjacent_to_cell(&self, cell: (usize, usize)) -> Vec<&EngineNumber> {
+        let mut numbers: Vec<&EngineNumber> = Vec::new();
+        for eng_num in self.numbers.iter() {
+            if eng_num
+                .surrounding_indices(&self.row_widths)
+                .contains(&cell)
+            {
+                numbers.push(eng_num);
+            }
+        }
+
+        return numbers;
+    }
+
+    fn symbols_adjacent_to_number(&self, eng_num: &EngineNumber) -> Vec<((usize, usize), char)> {
+        let mut symbols: Vec<((usize, usize), char)> = Vec::new();
+        for surr_indices in eng_num.surrounding_indices(&self.row_widths) {
+            if let Some(&sym) = self.symbols.get(&surr_indices) {
+                symbols.push((surr_indices, sym));
+            }
+        }
+
+        return symbols;
+    }
+
+    fn numbers_adjacent_to_symbol(&self, sym: char) -> Vec<&EngineNumber> {
+        let mut numbers: Vec<&EngineNumber> = Vec::new();
+        for eng_num in self.numbers.iter() {
+            if self
+                .symbols_adjacent_to_number(eng_num)
+                .iter()
+                .any(|&(_, s)| s == sym)
+            {
+                numbers.push(eng_num);
+            }
+        }
+
+        return numbers;
+    }
+
+    fn connected_components(&self) -> Vec<Vec<&EngineNumber>> {
+        fn find_root(parents: &mut [usize], i: usize) -> usize {
+            let mut root: usize = i;
+            while parents[root] != root {
+                root = parents[root];
+            }
+
+            let mut curr: usize = i;
+            while parents[curr] != root {
+                let next: usize = parents[curr];
+                parents[curr] = root;
+                curr = next;
+            }
+
+            return root;
+        }
+
+        let mut parents: Vec<usize> = (0..self.numbers.len()).collect();
+        let mut symbol_owners: HashMap<(usize, usize), usize> = HashMap::new();
+        for (i, eng_num) in self.numbers.iter().enumerate() {
+            for (sym_indices, _) in self.symbols_adjacent_to_number(eng_num) {
+                if let Some(&owner) = symbol_owners.get(&sym_indices) {
+                    let owner_root: usize = find_root(&mut parents, owner);
+                    let root: usize = find_root(&mut parents, i);
+                    parents[root] = owner_root;
+                } else {
+                    symbol_owners.insert(sym_indices, i);
                 }
-                break;
             }
         }
 
-        if is_part_num {
-            part_num_sum += eng_num.num;
+        let mut components: Vec<Vec<&EngineNumber>> = Vec::new();
+        let mut component_idxs: HashMap<usize, usize> = HashMap::new();
+        for (i, eng_num) in self.numbers.iter().enumerate() {
+            let root: usize = find_root(&mut parents, i);
+            if let Some(&component_idx) = component_idxs.get(&root) {
+                components[component_idx].push(eng_num);
+            } else {
+                component_idxs.insert(root, components.len());
+                components.push(vec![eng_num]);
+            }
+        }
+
+        return components;
+    }
+
+    fn part_numbers(&self) -> Vec<&EngineNumber> {
+        let mut part_nums: Vec<&EngineNumber> = Vec::new();
+        for eng_num in self.numbers.iter() {
+            if !self.symbols_adjacent_to_number(eng_num).is_empty() {
+                part_nums.push(eng_num);
+            }
+        }
+
+        return part_nums;
+    }
+
+    // Every symbol starts out as a gear with no parts, so arity rules can also
+    // match symbols that no number touches.
+    fn gears(&self, mode: &AdjacencyMode) -> HashMap<(usize, usize), EngineGear> {
+        let mut gears: HashMap<(usize, usize), EngineGear> = HashMap::new();
+        for (&sym_indices, &sym) in self.symbols.iter() {
+            gears.insert(
+                sym_indices,
+                EngineGear {
+                    sym: sym,
+                    part_nums: Vec::new(),
+                },
+            );
+        }
+
+        for eng_num in self.numbers.iter() {
+            let mut adjacent_symbols: Vec<((usize, usize), char)> =
+                self.symbols_adjacent_to_number(eng_num);
+            if let AdjacencyMode::FirstMatch = mode {
+                adjacent_symbols.truncate(1);
+            }
+
+            for (surr_indices, _) in adjacent_symbols {
+                gears
+                    .get_mut(&surr_indices)
+                    .expect("Should be able to look up gear")
+                    .part_nums
+                    .push(eng_num.num);
+            }
+        }
+
+        return gears;
+    }
+
+    fn gear_ratio_sum(&self, rule: &GearRule, mode: &AdjacencyMode) -> Option<u64> {
+        let mut ratio_sum: u64 = 0;
+        for (_, gear) in self.gears(mode) {
+            if rule.matches(&gear) {
+                ratio_sum = ratio_sum.checked_add(rule.ratio(&gear)?)?;
+            }
         }
+
+        return Some(ratio_sum);
     }
+}
 
-    let mut valid_gears_ratio_sum: u32 = 0;
-    for (_, gear) in gears {
-        if gear.n_part_nums == 2 {
-            valid_gears_ratio_sum += gear.ratio;
+fn parse_cell(s: &str) -> (usize, usize) {
+    let (row_str, col_str) = s.split_once(',').expect("Cell should be given as row,col");
+    let row_idx: usize = row_str
+        .trim()
+        .parse::<usize>()
+        .expect("Row index should be non-negative integer");
+    let col_idx: usize = col_str
+        .trim()
+        .parse::<usize>()
+        .expect("Column index should be non-negative integer");
+
+    return (row_idx, col_idx);
+}
+
+fn main() {
+    let filename: &str = "../schematic.txt";
+    let file_contents: String = read_file(filename);
+    let schematic: Schematic = Schematic::parse(&file_contents);
+
+    let mut gear_rule = GearRule {
+        symbols: vec!['*'],
+        arity: GearArity::Exact(2),
+        aggregation: GearAggregation::Product,
+    };
+
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let mut adjacency_mode: AdjacencyMode = AdjacencyMode::AllMatches;
+    if !args.is_empty() && args[0] == "--first-match" {
+        adjacency_mode = AdjacencyMode::FirstMatch;
+        args.remove(0);
+    }
+
+    while args.len() > 1 && args[0].starts_with("--gear-") {
+        match args[0].as_str() {
+            "--gear-symbols" => gear_rule.symbols = args[1].chars().collect(),
+            "--gear-parts" => gear_rule.arity = parse_gear_arity(&args[1]),
+            "--gear-aggregation" => gear_rule.aggregation = parse_gear_aggregation(&args[1]),
+            _ => panic!(
+                "Gear option should be one of --gear-symbols, --gear-parts or --gear-aggregation"
+            ),
         }
+
+        args.drain(0..2);
     }
 
+    if !args.is_empty() {
+        match args[0].as_str() {
+            "--numbers-near" => {
+                let cell: (usize, usize) =
+                    parse_cell(args.get(1).expect("Query should have a cell argument"));
+                for eng_num in schematic.numbers_adjacent_to_cell(cell) {
+                    println!("{}", eng_num);
+                }
+            }
+            "--symbols-near" => {
+                let cell: (usize, usize) =
+                    parse_cell(args.get(1).expect("Query should have a cell argument"));
+                let eng_num: &EngineNumber = schematic
+                    .number_at(cell)
+                    .expect("Should be able to find number at cell");
+                for ((row_idx, col_idx), sym) in schematic.symbols_adjacent_to_number(eng_num) {
+                    println!("{} at ({}, {})", sym, row_idx, col_idx);
+                }
+            }
+            "--numbers-near-symbol" => {
+                let sym: char = args
+                    .get(1)
+                    .expect("Query should have a symbol argument")
+                    .chars()
+                    .next()
+                    .expect("Symbol should not be empty");
+                for eng_num in schematic.numbers_adjacent_to_symbol(sym) {
+                    println!("{}", eng_num);
+                }
+            }
+            "--components" => {
+                for component in schematic.connected_components() {
+                    let num_strs: Vec<String> =
+                        component.iter().map(|n| n.num.to_string()).collect();
+                    println!("{}", num_strs.join(" "));
+                }
+            }
+            "--render" => {
+                let format: render::RenderFormat =
+                    match args.get(1).expect("Query should have a format argument").as_str() {
+                        "ansi" => render::RenderFormat::Ansi,
+                        "html" => render::RenderFormat::Html,
+                        _ => panic!("Render format should be ansi or html"),
+                    };
+                print!(
+                    "{}",
+                    render::render(&schematic, &gear_rule, &adjacency_mode, &format)
+                );
+            }
+            _ => panic!(
+                "Query should be one of --numbers-near, --symbols-near, --numbers-near-symbol, --components or --render"
+            ),
+        }
+
+        return;
+    }
+
+    let part_num_sum: u64 = schematic.part_numbers().iter().map(|n| n.num).sum();
+    let valid_gears_ratio_sum: u64 = schematic
+        .gear_ratio_sum(&gear_rule, &adjacency_mode)
+        .expect("Gear ratio sum should not overflow");
+
     println!("{}", part_num_sum);
     println!("{}", valid_gears_ratio_sum);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star_gears() -> GearRule {
+        return GearRule {
+            symbols: vec!['*'],
+            arity: GearArity::Exact(2),
+            aggregation: GearAggregation::Product,
+        };
+    }
+
+    #[test]
+    fn shared_number_counts_towards_every_gear() {
+        let schematic: Schematic = Schematic::parse("2*3*4");
+        let rule: GearRule = star_gears();
+
+        assert_eq!(
+            schematic.gear_ratio_sum(&rule, &AdjacencyMode::AllMatches),
+            Some((2 * 3) + (3 * 4))
+        );
+        assert_eq!(
+            schematic.gear_ratio_sum(&rule, &AdjacencyMode::FirstMatch),
+            Some(2 * 3)
+        );
+    }
+
+    #[test]
+    fn ragged_rows_skip_missing_cells() {
+        let schematic: Schematic = Schematic::parse("....12\n...*\n..7");
+        assert_eq!(schematic.row_widths, vec![6, 4, 3]);
+
+        let eng_num: &EngineNumber = schematic
+            .number_at((0, 4))
+            .expect("Should be able to find number at cell");
+        let surr_indices: Vec<(usize, usize)> = eng_num.surrounding_indices(&schematic.row_widths);
+        assert!(surr_indices.contains(&(1, 3)));
+        assert!(surr_indices
+            .iter()
+            .all(|&(row_idx, col_idx)| col_idx < schematic.row_widths[row_idx]));
+
+        let part_nums: Vec<u64> = schematic.part_numbers().iter().map(|n| n.num).collect();
+        assert_eq!(part_nums, vec![12, 7]);
+    }
+
+    #[test]
+    fn tabs_are_padding() {
+        let schematic: Schematic = Schematic::parse("467\t\t114\n\t\t*\t\t\n\t35\t\t\t");
+        assert!(is_padding('\t'));
+        assert_eq!(schematic.symbols.len(), 1);
+        assert_eq!(schematic.symbols.get(&(1, 2)), Some(&'*'));
+
+        let part_nums: Vec<u64> = schematic.part_numbers().iter().map(|n| n.num).collect();
+        assert_eq!(part_nums, vec![467, 35]);
+    }
+
+    #[test]
+    fn trailing_newline_adds_no_row() {
+        let schematic: Schematic = Schematic::parse("467..\n...*.\n..35.\n");
+        let crlf_schematic: Schematic = Schematic::parse("467..\r\n...*.\r\n..35.\r\n");
+        assert_eq!(schematic.row_widths, vec![5, 5, 5]);
+        assert_eq!(crlf_schematic.row_widths, vec![5, 5, 5]);
+        assert_eq!(crlf_schematic.symbols.len(), 1);
+
+        let rule: GearRule = star_gears();
+        assert_eq!(
+            schematic.gear_ratio_sum(&rule, &AdjacencyMode::AllMatches),
+            Some(467 * 35)
+        );
+        assert_eq!(
+            crlf_schematic.gear_ratio_sum(&rule, &AdjacencyMode::AllMatches),
+            Some(467 * 35)
+        );
+    }
+
+    #[test]
+    fn arity_counts_symbols_without_parts() {
+        let schematic: Schematic = Schematic::parse("12*..*\n......");
+        let rule = GearRule {
+            symbols: vec!['*'],
+            arity: GearArity::Max(1),
+            aggregation: GearAggregation::Sum,
+        };
+
+        let matching_gears: usize = schematic
+            .gears(&AdjacencyMode::AllMatches)
+            .values()
+            .filter(|gear| rule.matches(gear))
+            .count();
+        assert_eq!(matching_gears, 2);
+        assert_eq!(
+            schematic.gear_ratio_sum(&rule, &AdjacencyMode::AllMatches),
+            Some(12)
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{AdjacencyMode, GearRule, Schematic};
+use std::collections::HashMap;
+
+pub enum RenderFormat {
+    Ansi,
+    Html,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CellKind {
+    Blank,
+    PartNumber,
+    NonPartNumber,
+    Symbol,
+    GearSymbol,
+    ValidGear,
+}
+
+impl CellKind {
+    fn ansi_code(&self) -> &str {
+        let code: &str = match self {
+            CellKind::Blank => "2",
+            CellKind::PartNumber => "32",
+            CellKind::NonPartNumber => "31",
+            CellKind::Symbol => "0",
+            CellKind::GearSymbol => "33",
+            CellKind::ValidGear => "1;35",
+        };
+
+        return code;
+    }
+
+    fn html_class(&self) -> &str {
+        let class: &str = match self {
+            CellKind::Blank => "blank",
+            CellKind::PartNumber => "part",
+            CellKind::NonPartNumber => "non-part",
+            CellKind::Symbol => "symbol",
+            CellKind::GearSymbol => "gear",
+            CellKind::ValidGear => "valid-gear",
+        };
+
+        return class;
+    }
+}
+
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Engine Schematic</title>
+<style>
+body { background: #0f0f23; color: #cccccc; font-family: monospace; }
+.blank { color: #555555; }
+.part { color: #00cc00; }
+.non-part { color: #ff4444; }
+.symbol { color: #ffffff; }
+.gear { color: #ffff66; }
+.valid-gear { color: #ff66ff; font-weight: bold; }
+</style>
+</head>
+<body>
+<p>
+<span class="part">part number</span>
+<span class="non-part">non-part number</span>
+<span class="gear">gear symbol</span>
+<span class="valid-gear">valid gear</span>
+</p>
+<pre>
+"#;
+
+const HTML_FOOTER: &str = "</pre>
+</body>
+</html>
+";
+
+fn escape_html(c: char) -> String {
+    let escaped: String = match c {
+        '&' => String::from("&amp;"),
+        '<' => String::from("&lt;"),
+        '>' => String::from("&gt;"),
+        '"' => String::from("&quot;"),
+        _ => c.to_string(),
+    };
+
+    return escaped;
+}
+
+fn classify_cells(
+    schematic: &Schematic,
+    rule: &GearRule,
+    mode: &AdjacencyMode,
+) -> HashMap<(usize, usize), CellKind> {
+    let mut kinds: HashMap<(usize, usize), CellKind> = HashMap::new();
+    for eng_num in schematic.numbers.iter() {
+        let kind: CellKind = if schematic.symbols_adjacent_to_number(eng_num).is_empty() {
+            CellKind::NonPartNumber
+        } else {
+            CellKind::PartNumber
+        };
+
+        for col_idx in eng_num.start_col_idx..eng_num.end_col_idx {
+            kinds.insert((eng_num.row_idx, col_idx), kind);
+        }
+    }
+
+    for (&cell, &sym) in schematic.symbols.iter() {
+        if rule.symbols.contains(&sym) {
+            kinds.insert(cell, CellKind::GearSymbol);
+        } else {
+            kinds.insert(cell, CellKind::Symbol);
+        }
+    }
+
+    for (cell, gear) in schematic.gears(mode) {
+        if rule.matches(&gear) {
+            kinds.insert(cell, CellKind::ValidGear);
+        }
+    }
+
+    return kinds;
+}
+
+pub fn render(
+    schematic: &Schematic,
+    rule: &GearRule,
+    mode: &AdjacencyMode,
+    format: &RenderFormat,
+) -> String {
+    let kinds: HashMap<(usize, usize), CellKind> = classify_cells(schematic, rule, mode);
+
+    let mut rendered: String = String::new();
+    if let RenderFormat::Html = format {
+        rendered.push_str(HTML_HEADER);
+    }
+
+    for (row_idx, row) in schematic.rows.iter().enumerate() {
+        let mut col_idx: usize = 0;
+        while col_idx < row.len() {
+            let kind: CellKind = *kinds.get(&(row_idx, col_idx)).unwrap_or(&CellKind::Blank);
+            let mut run: String = String::new();
+            while col_idx < row.len()
+                && *kinds.get(&(row_idx, col_idx)).unwrap_or(&CellKind::Blank) == kind
+            {
+                match format {
+                    RenderFormat::Ansi => run.push(row[col_idx]),
+                    RenderFormat::Html => run.push_str(&escape_html(row[col_idx])),
+                }
+                col_idx += 1;
+            }
+
+            match format {
+                RenderFormat::Ansi => {
+                    rendered.push_str(&format!("\x1b[{}m{}\x1b[0m", kind.ansi_code(), run))
+                }
+                RenderFormat::Html => rendered.push_str(&format!(
+                    "<span class=\"{}\">{}</span>",
+                    kind.html_class(),
+                    run
+                )),
+            }
+        }
+
+        rendered.push('\n');
+    }
+
+    if let RenderFormat::Html = format {
+        rendered.push_str(HTML_FOOTER);
+    }
+
+    return rendered;
+}
@@ -1,14 +1,52 @@
+mod scoring;
+mod validate;
+
 use lazy_static::lazy_static;
 use regex::Regex;
+use scoring::{PointsRule, ScoringRule, WinCards};
 use std::{
     collections::{HashMap, VecDeque},
-    fs,
+    env, fmt, fs,
+    iter::zip,
     str::Split,
 };
 
 struct ScratchCard {
     id: u32,
-    winning_cards: Vec<u32>,
+    winning_nums: Vec<u32>,
+    held_nums: Vec<u32>,
+    matches: Vec<u32>,
+}
+
+#[derive(Clone, Copy)]
+enum OutOfRangePolicy {
+    Clamp,
+    Error,
+}
+
+fn parse_out_of_range_policy(s: &str) -> OutOfRangePolicy {
+    let policy: OutOfRangePolicy = match s {
+        "clamp" => OutOfRangePolicy::Clamp,
+        "error" => OutOfRangePolicy::Error,
+        _ => panic!("Out of range policy should be clamp or error"),
+    };
+
+    return policy;
+}
+
+impl OutOfRangePolicy {
+    // Only wins past the last card are clamped; a card missing from inside
+    // the table is always an error.
+    fn clamps(&self, won_card_id: u32, max_card_id: u32) -> bool {
+        return matches!(self, OutOfRangePolicy::Clamp) && won_card_id > max_card_id;
+    }
+}
+
+struct CardReport<'a> {
+    card: &'a ScratchCard,
+    points: u64,
+    copies: u64,
+    won_from: Vec<(u32, u64)>,
 }
 
 fn read_file(filename: &str) -> String {
@@ -34,6 +72,7 @@ fn parse_scratch_card(line: &str) -> ScratchCard {
         .parse::<u32>()
         .expect("Should be able to parse card ID");
 
+    let mut held_nums: Vec<u32> = Vec::new();
     let mut scratched_nums: HashMap<u32, bool> = HashMap::new();
 
     for scratched_num_str in RE_SPACE.split(
@@ -42,67 +81,311 @@ fn parse_scratch_card(line: &str) -> ScratchCard {
             .as_str(),
     ) {
         if let Ok(scratched_num) = scratched_num_str.parse::<u32>() {
+            held_nums.push(scratched_num);
             scratched_nums.insert(scratched_num, true);
         }
     }
 
-    let mut n_wins: u32 = 0;
+    let mut winning_nums: Vec<u32> = Vec::new();
+    let mut matches: Vec<u32> = Vec::new();
     for winning_num_str in RE_SPACE.split(
         caps.get(2)
             .expect("Should be able to capture groups")
             .as_str(),
     ) {
         if let Ok(winning_num) = winning_num_str.parse::<u32>() {
+            winning_nums.push(winning_num);
             if scratched_nums.contains_key(&winning_num) {
-                n_wins += 1;
+                matches.push(winning_num);
             }
         }
     }
 
     let scratch_card = ScratchCard {
         id: card_id,
-        winning_cards: ((card_id + 1)..(card_id + n_wins + 1)).collect(),
+        winning_nums: winning_nums,
+        held_nums: held_nums,
+        matches: matches,
     };
 
     return scratch_card;
 }
 
-fn main() {
-    let filename: &str = "../scratchcards.txt";
-    let file_contents: String = read_file(filename);
-    let lines: Split<char> = file_contents.split::<char>('\n');
+fn card_copies(scratch_cards: &[ScratchCard], rule: &WinCards) -> Vec<(u64, Vec<(u32, u64)>)> {
+    let mut card_idxs: HashMap<u32, usize> = HashMap::new();
+    for (i, scratch_card) in scratch_cards.iter().enumerate() {
+        card_idxs.insert(scratch_card.id, i);
+    }
+    let max_card_id: u32 = card_idxs.keys().copied().max().unwrap_or(0);
 
-    let mut scratch_queue: VecDeque<u32> = VecDeque::new();
-    let mut scratch_map: HashMap<u32, ScratchCard> = HashMap::new();
-    let mut points_won: u32 = 0;
+    let mut copies: Vec<(u64, Vec<(u32, u64)>)> = Vec::new();
+    for _ in scratch_cards.iter() {
+        copies.push((1, Vec::new()));
+    }
 
-    for line in lines {
-        let scratch_card = parse_scratch_card(line);
-        if scratch_card.winning_cards.len() > 0 {
-            points_won += u32::pow(2, (scratch_card.winning_cards.len() as u32) - 1);
+    // Cards only win cards with higher IDs, so visiting them in ID order
+    // settles every card's copies before they are passed on.
+    let mut sorted_idxs: Vec<usize> = (0..scratch_cards.len()).collect();
+    sorted_idxs.sort_by_key(|&i| scratch_cards[i].id);
+
+    for i in sorted_idxs {
+        let scratch_card: &ScratchCard = &scratch_cards[i];
+        let n_copies: u64 = copies[i].0;
+        for winning_scratch_card_id in rule.won_cards(scratch_card) {
+            let winning_idx: usize = match card_idxs.get(&winning_scratch_card_id) {
+                Some(&idx) => idx,
+                None => {
+                    if rule
+                        .out_of_range
+                        .clamps(winning_scratch_card_id, max_card_id)
+                    {
+                        continue;
+                    }
+
+                    panic!(
+                        "Card {} wins card {} which is not in the table",
+                        scratch_card.id, winning_scratch_card_id
+                    );
+                }
+            };
+            let (winning_copies, won_from) = &mut copies[winning_idx];
+            *winning_copies = winning_copies
+                .checked_add(n_copies)
+                .expect("Number of cards won should not overflow");
+            if let Some(max_copies) = rule.max_copies {
+                *winning_copies = u64::min(*winning_copies, max_copies);
+            }
+            won_from.push((scratch_card.id, n_copies));
         }
+    }
+
+    return copies;
+}
+
+fn card_report<'a>(
+    scratch_cards: &'a [ScratchCard],
+    points_rule: &dyn PointsRule,
+    cards_rule: &WinCards,
+) -> Vec<CardReport<'a>> {
+    let mut report: Vec<CardReport> = Vec::new();
+    for (scratch_card, (copies, won_from)) in
+        zip(scratch_cards, card_copies(scratch_cards, cards_rule))
+    {
+        report.push(CardReport {
+            card: scratch_card,
+            points: points_rule.card_points(scratch_card.matches.len() as u32),
+            copies: copies,
+            won_from: won_from,
+        });
+    }
+
+    return report;
+}
 
+impl fmt::Display for CardReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let match_strs: Vec<String> = self.card.matches.iter().map(|n| n.to_string()).collect();
+        let won_from_strs: Vec<String> = self
+            .won_from
+            .iter()
+            .map(|(id, n)| format!("{} (x{})", id, n))
+            .collect();
+
+        return write!(
+            f,
+            "Card {}: {} matches [{}] of {} winning and {} held numbers, {} points, {} copies, won from [{}]",
+            self.card.id,
+            self.card.matches.len(),
+            match_strs.join(" "),
+            self.card.winning_nums.len(),
+            self.card.held_nums.len(),
+            self.points,
+            self.copies,
+            won_from_strs.join(", ")
+        );
+    }
+}
+
+fn count_cards_queue(scratch_cards: &[ScratchCard], rule: &WinCards) -> u64 {
+    let mut scratch_queue: VecDeque<u32> = VecDeque::new();
+    let mut scratch_map: HashMap<u32, &ScratchCard> = HashMap::new();
+    let mut scratch_copies: HashMap<u32, u64> = HashMap::new();
+    for scratch_card in scratch_cards.iter() {
         scratch_queue.push_back(scratch_card.id);
         scratch_map.insert(scratch_card.id, scratch_card);
+        scratch_copies.insert(scratch_card.id, 1);
     }
+    let max_card_id: u32 = scratch_copies.keys().copied().max().unwrap_or(0);
+
+    let mut cards_won: u64 = 0;
+    while let Some(scratch_card_id) = scratch_queue.pop_front() {
+        for winning_scratch_card_id in rule.won_cards(
+            scratch_map
+                .get(&scratch_card_id)
+                .expect("Should be able to look up scratch card"),
+        ) {
+            let winning_copies: &mut u64 = match scratch_copies.get_mut(&winning_scratch_card_id) {
+                Some(n) => n,
+                None => {
+                    if rule
+                        .out_of_range
+                        .clamps(winning_scratch_card_id, max_card_id)
+                    {
+                        continue;
+                    }
+
+                    panic!(
+                        "Card {} wins card {} which is not in the table",
+                        scratch_card_id, winning_scratch_card_id
+                    );
+                }
+            };
+
+            if rule
+                .max_copies
+                .is_some_and(|max_copies| *winning_copies >= max_copies)
+            {
+                continue;
+            }
 
-    let mut cards_won: u32 = 0;
-    while scratch_queue.len() > 0 {
-        let scratch_card_id = scratch_queue
-            .pop_front()
-            .expect("Should be able to dequeue");
-        for winning_scratch_card_id in scratch_map
-            .get(&scratch_card_id)
-            .expect("Should be able to look up scratch card")
-            .winning_cards
-            .clone()
-        {
+            *winning_copies += 1;
             scratch_queue.push_back(winning_scratch_card_id);
         }
 
         cards_won += 1;
     }
 
+    return cards_won;
+}
+
+fn main() {
+    let filename: &str = "../scratchcards.txt";
+    let file_contents: String = read_file(filename);
+    let lines: Split<char> = file_contents.split::<char>('\n');
+    let args: Vec<String> = env::args().skip(1).collect();
+    let use_queue: bool = args.iter().any(|arg| arg == "--queue");
+    let show_report: bool = args.iter().any(|arg| arg == "--report");
+    let show_issues: bool = args.iter().any(|arg| arg == "--validate");
+    let mut points_rule: Box<dyn PointsRule> = Box::new(scoring::DoublingPoints);
+    let mut cards_rule: WinCards = WinCards::new();
+    let mut out_of_range: OutOfRangePolicy = OutOfRangePolicy::Error;
+    for (i, arg) in args.iter().enumerate() {
+        match arg.as_str() {
+            "--points" => {
+                points_rule = scoring::parse_points_rule(
+                    args.get(i + 1).expect("Points option should have a rule"),
+                )
+            }
+            "--cards" => {
+                cards_rule = scoring::parse_cards_rule(
+                    args.get(i + 1).expect("Cards option should have a rule"),
+                )
+            }
+            "--out-of-range" => {
+                out_of_range = parse_out_of_range_policy(
+                    args.get(i + 1)
+                        .expect("Out of range option should have a policy"),
+                )
+            }
+            _ => {}
+        }
+    }
+    cards_rule.out_of_range = out_of_range;
+
+    let mut scratch_cards: Vec<ScratchCard> = Vec::new();
+    for line in lines {
+        scratch_cards.push(parse_scratch_card(line));
+    }
+
+    if show_issues {
+        let issues: Vec<validate::CardIssue> = validate::validate(&scratch_cards, &cards_rule);
+        for issue in issues.iter() {
+            println!("{}", issue);
+        }
+
+        if issues.is_empty() {
+            println!("No issues found");
+        }
+
+        return;
+    }
+
+    if show_report {
+        for card in card_report(&scratch_cards, points_rule.as_ref(), &cards_rule) {
+            println!("{}", card);
+        }
+
+        return;
+    }
+
+    let cards_won: u64 = if use_queue {
+        count_cards_queue(&scratch_cards, &cards_rule)
+    } else {
+        cards_rule.score(&scratch_cards)
+    };
+    let points_won: u64 = points_rule.score(&scratch_cards);
+
     println!("{}", points_won);
     println!("{}", cards_won);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_scratch_cards(lines: &[&str]) -> Vec<ScratchCard> {
+        return lines.iter().map(|line| parse_scratch_card(line)).collect();
+    }
+
+    fn assert_matches_queue(lines: &[&str]) {
+        let scratch_cards: Vec<ScratchCard> = parse_scratch_cards(lines);
+        assert_eq!(
+            WinCards::new().score(&scratch_cards),
+            count_cards_queue(&scratch_cards, &WinCards::new())
+        );
+    }
+
+    #[test]
+    fn card_copies_matches_queue_on_sample() {
+        assert_matches_queue(&[
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53",
+            "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+            "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1",
+            "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83",
+            "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36",
+            "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+        ]);
+    }
+
+    #[test]
+    fn card_copies_matches_queue_out_of_order() {
+        let lines: [&str; 4] = [
+            "Card 1: 1 2 | 1 2",
+            "Card 3: 5 | 5",
+            "Card 2: 9 | 9",
+            "Card 4: 7 | 8",
+        ];
+        assert_matches_queue(&lines);
+        assert_eq!(WinCards::new().score(&parse_scratch_cards(&lines)), 12);
+    }
+
+    #[test]
+    fn card_copies_matches_queue_with_rule() {
+        let scratch_cards: Vec<ScratchCard> = parse_scratch_cards(&[
+            "Card 1: 1 2 3 | 1 2 3",
+            "Card 2: 4 5 | 4 5",
+            "Card 3: 6 | 6",
+            "Card 4: 7 | 7",
+            "Card 5: 8 | 9",
+        ]);
+        let rule = WinCards {
+            offset: 2,
+            max_copies: Some(3),
+            out_of_range: OutOfRangePolicy::Clamp,
+        };
+        assert_eq!(
+            rule.score(&scratch_cards),
+            count_cards_queue(&scratch_cards, &rule)
+        );
+    }
+}
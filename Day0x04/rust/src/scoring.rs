@@ -0,0 +1,163 @@
+use crate::{card_copies, OutOfRangePolicy, ScratchCard};
+
+pub trait ScoringRule {
+    fn score(&self, scratch_cards: &[ScratchCard]) -> u64;
+}
+
+pub struct DoublingPoints;
+
+pub struct LinearPoints {
+    pub points_per_match: u64,
+}
+
+pub struct FibonacciPoints;
+
+pub struct WinCards {
+    pub offset: u32,
+    pub max_copies: Option<u64>,
+    pub out_of_range: OutOfRangePolicy,
+}
+
+pub trait PointsRule {
+    fn card_points(&self, n_matches: u32) -> u64;
+}
+
+impl<T: PointsRule + ?Sized> ScoringRule for T {
+    fn score(&self, scratch_cards: &[ScratchCard]) -> u64 {
+        let points: u64 = scratch_cards
+            .iter()
+            .try_fold(0, |acc: u64, card| {
+                acc.checked_add(self.card_points(card.matches.len() as u32))
+            })
+            .expect("Points won should not overflow");
+
+        return points;
+    }
+}
+
+impl PointsRule for DoublingPoints {
+    fn card_points(&self, n_matches: u32) -> u64 {
+        if n_matches == 0 {
+            return 0;
+        }
+
+        return u64::checked_pow(2, n_matches - 1).expect("Points won should not overflow");
+    }
+}
+
+impl PointsRule for LinearPoints {
+    fn card_points(&self, n_matches: u32) -> u64 {
+        return self
+            .points_per_match
+            .checked_mul(n_matches as u64)
+            .expect("Points won should not overflow");
+    }
+}
+
+impl PointsRule for FibonacciPoints {
+    fn card_points(&self, n_matches: u32) -> u64 {
+        let mut curr: u64 = 0;
+        let mut next: u64 = 1;
+        for _ in 0..n_matches {
+            let sum: u64 = curr
+                .checked_add(next)
+                .expect("Points won should not overflow");
+            curr = next;
+            next = sum;
+        }
+
+        return curr;
+    }
+}
+
+impl WinCards {
+    pub fn new() -> WinCards {
+        return WinCards {
+            offset: 1,
+            max_copies: None,
+            out_of_range: OutOfRangePolicy::Error,
+        };
+    }
+
+    pub fn won_cards(&self, scratch_card: &ScratchCard) -> Vec<u32> {
+        let first_id: u32 = scratch_card
+            .id
+            .checked_add(self.offset)
+            .expect("Won card ID should not overflow");
+        let n_matches: u32 = scratch_card.matches.len() as u32;
+        let end_id: u32 = first_id
+            .checked_add(n_matches)
+            .expect("Won card ID should not overflow");
+
+        return (first_id..end_id).collect();
+    }
+}
+
+impl ScoringRule for WinCards {
+    fn score(&self, scratch_cards: &[ScratchCard]) -> u64 {
+        let cards_won: u64 = card_copies(scratch_cards, self)
+            .iter()
+            .try_fold(0, |acc: u64, (n, _)| acc.checked_add(*n))
+            .expect("Number of cards won should not overflow");
+
+        return cards_won;
+    }
+}
+
+pub fn parse_points_rule(s: &str) -> Box<dyn PointsRule> {
+    if let Some(n_str) = s.strip_prefix("linear=") {
+        return Box::new(LinearPoints {
+            points_per_match: n_str
+                .parse::<u64>()
+                .expect("Points per match should be positive integer"),
+        });
+    }
+
+    let rule: Box<dyn PointsRule> = match s {
+        "doubling" => Box::new(DoublingPoints),
+        "linear" => Box::new(LinearPoints {
+            points_per_match: 1,
+        }),
+        "fibonacci" => Box::new(FibonacciPoints),
+        _ => panic!("Points rule should be one of doubling, linear[=N] or fibonacci"),
+    };
+
+    return rule;
+}
+
+pub fn parse_cards_rule(s: &str) -> WinCards {
+    let mut rule = WinCards::new();
+
+    for option_str in s.split::<char>(',') {
+        if option_str == "next" {
+            continue;
+        }
+
+        let (key, value) = option_str
+            .split_once('=')
+            .expect("Cards rule should be given as next or key=value pairs");
+        match key {
+            "offset" => {
+                let offset: u32 = value
+                    .parse::<u32>()
+                    .expect("Offset should be positive integer");
+                if offset == 0 {
+                    panic!("Offset should be positive integer");
+                }
+                rule.offset = offset;
+            }
+            "cap" => {
+                let max_copies: u64 = value
+                    .parse::<u64>()
+                    .expect("Copies cap should be positive integer");
+                if max_copies == 0 {
+                    panic!("Copies cap should be positive integer");
+                }
+                rule.max_copies = Some(max_copies);
+            }
+            _ => panic!("Cards rule option should be offset or cap"),
+        }
+    }
+
+    return rule;
+}
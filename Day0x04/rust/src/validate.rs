@@ -0,0 +1,111 @@
+use crate::{scoring::WinCards, ScratchCard};
+use std::{collections::HashSet, fmt};
+
+pub enum CardIssue {
+    DuplicateWinningNumber { card_id: u32, num: u32 },
+    DuplicateHeldNumber { card_id: u32, num: u32 },
+    DuplicateCard { card_id: u32 },
+    MissingCard { card_id: u32 },
+    OutOfOrderCard { card_id: u32, prev_card_id: u32 },
+    WinOutOfRange { card_id: u32, won_card_id: u32 },
+}
+
+impl fmt::Display for CardIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            CardIssue::DuplicateWinningNumber { card_id, num } => {
+                write!(f, "Card {}: duplicate winning number {}", card_id, num)
+            }
+            CardIssue::DuplicateHeldNumber { card_id, num } => {
+                write!(f, "Card {}: duplicate held number {}", card_id, num)
+            }
+            CardIssue::DuplicateCard { card_id } => {
+                write!(f, "Card {}: appears more than once", card_id)
+            }
+            CardIssue::MissingCard { card_id } => write!(f, "Card {}: missing", card_id),
+            CardIssue::OutOfOrderCard {
+                card_id,
+                prev_card_id,
+            } => write!(f, "Card {}: appears after card {}", card_id, prev_card_id),
+            CardIssue::WinOutOfRange {
+                card_id,
+                won_card_id,
+            } => write!(
+                f,
+                "Card {}: wins card {} which is not in the table",
+                card_id, won_card_id
+            ),
+        };
+    }
+}
+
+fn find_duplicates(nums: &[u32]) -> Vec<u32> {
+    let mut seen: HashSet<u32> = HashSet::new();
+    let mut duplicates: Vec<u32> = Vec::new();
+    for &num in nums.iter() {
+        if !seen.insert(num) && !duplicates.contains(&num) {
+            duplicates.push(num);
+        }
+    }
+
+    return duplicates;
+}
+
+pub fn validate(scratch_cards: &[ScratchCard], rule: &WinCards) -> Vec<CardIssue> {
+    let mut issues: Vec<CardIssue> = Vec::new();
+
+    let mut card_ids: HashSet<u32> = HashSet::new();
+    let mut prev_card_id: Option<u32> = None;
+    for scratch_card in scratch_cards.iter() {
+        for num in find_duplicates(&scratch_card.winning_nums) {
+            issues.push(CardIssue::DuplicateWinningNumber {
+                card_id: scratch_card.id,
+                num: num,
+            });
+        }
+
+        for num in find_duplicates(&scratch_card.held_nums) {
+            issues.push(CardIssue::DuplicateHeldNumber {
+                card_id: scratch_card.id,
+                num: num,
+            });
+        }
+
+        if !card_ids.insert(scratch_card.id) {
+            issues.push(CardIssue::DuplicateCard {
+                card_id: scratch_card.id,
+            });
+        }
+
+        if let Some(prev_id) = prev_card_id {
+            if scratch_card.id < prev_id {
+                issues.push(CardIssue::OutOfOrderCard {
+                    card_id: scratch_card.id,
+                    prev_card_id: prev_id,
+                });
+            }
+        }
+        prev_card_id = Some(scratch_card.id);
+    }
+
+    if let Some(&max_card_id) = card_ids.iter().max() {
+        for card_id in 1..max_card_id {
+            if !card_ids.contains(&card_id) {
+                issues.push(CardIssue::MissingCard { card_id: card_id });
+            }
+        }
+    }
+
+    for scratch_card in scratch_cards.iter() {
+        for won_card_id in rule.won_cards(scratch_card) {
+            if !card_ids.contains(&won_card_id) {
+                issues.push(CardIssue::WinOutOfRange {
+                    card_id: scratch_card.id,
+                    won_card_id: won_card_id,
+                });
+            }
+        }
+    }
+
+    return issues;
+}
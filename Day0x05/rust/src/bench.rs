@@ -0,0 +1,78 @@
+use crate::{AlmanacConverter, AlmanacMap};
+use std::time::{Duration, Instant};
+
+struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+
+        return self.state >> 16;
+    }
+}
+
+fn generate_converter(rng: &mut Lcg, n_maps: usize) -> AlmanacConverter {
+    let mut maps: Vec<AlmanacMap> = Vec::new();
+    let mut src_start: u64 = 0;
+    for _ in 0..n_maps {
+        src_start += rng.next() % 1000;
+        let length: u64 = 1 + (rng.next() % 100000);
+        maps.push(AlmanacMap {
+            dest_start: rng.next() % (1 << 40),
+            src_start: src_start,
+            length: length,
+        });
+        src_start += length;
+    }
+
+    return AlmanacConverter::new(maps);
+}
+
+fn time_lookups(converter: &AlmanacConverter, keys: &[u64], linear: bool) -> (Duration, u64) {
+    let start: Instant = Instant::now();
+    let mut checksum: u64 = 0;
+    for &k in keys.iter() {
+        let v: u64 = if linear {
+            converter.get_linear(k)
+        } else {
+            converter.get(k)
+        };
+        checksum = checksum.wrapping_add(v);
+    }
+
+    return (start.elapsed(), checksum);
+}
+
+pub fn run_benchmark() {
+    let mut rng = Lcg { state: 2023 };
+    let n_keys: usize = 100000;
+
+    println!("maps\tlinear\tbinary\tspeedup");
+    for n_maps in [10, 100, 1000, 5000, 10000] {
+        let converter: AlmanacConverter = generate_converter(&mut rng, n_maps);
+        let src_end: u64 = *converter
+            .max_src_ends
+            .last()
+            .expect("Converter should have maps");
+        let keys: Vec<u64> = (0..n_keys).map(|_| rng.next() % (src_end + 1000)).collect();
+
+        let (linear_time, linear_checksum) = time_lookups(&converter, &keys, true);
+        let (binary_time, binary_checksum) = time_lookups(&converter, &keys, false);
+        if linear_checksum != binary_checksum {
+            panic!("Binary search lookup should match linear lookup");
+        }
+
+        println!(
+            "{}\t{:?}\t{:?}\t{:.1}x",
+            n_maps,
+            linear_time,
+            binary_time,
+            linear_time.as_secs_f64() / binary_time.as_secs_f64()
+        );
+    }
+}
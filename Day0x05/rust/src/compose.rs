@@ -0,0 +1,131 @@
+use crate::{subtract_intervals, AlmanacConverter, AlmanacSection, Interval};
+use std::fmt;
+
+struct ComposedPiece {
+    start: u64,
+    end: u64,
+    offset: i128,
+}
+
+impl ComposedPiece {
+    fn apply(&self, k: u64) -> u64 {
+        return ((k as i128) + self.offset) as u64;
+    }
+}
+
+pub struct ComposedConverter {
+    pieces: Vec<ComposedPiece>,
+}
+
+fn converter_pieces(converter: &AlmanacConverter) -> Vec<ComposedPiece> {
+    let mut pieces: Vec<ComposedPiece> = Vec::new();
+    let mut claimed: Vec<Interval> = Vec::new();
+    for map in converter.maps.iter() {
+        let src_range: Interval = (map.src_start, map.src_start.saturating_add(map.length));
+        for (start, end) in subtract_intervals(&[src_range], &claimed) {
+            pieces.push(ComposedPiece {
+                start: start,
+                end: end,
+                offset: (map.dest_start as i128) - (map.src_start as i128),
+            });
+        }
+
+        claimed.push(src_range);
+    }
+
+    for (start, end) in subtract_intervals(&[(0, u64::MAX)], &claimed) {
+        pieces.push(ComposedPiece {
+            start: start,
+            end: end,
+            offset: 0,
+        });
+    }
+
+    pieces.sort_by_key(|p| p.start);
+
+    return pieces;
+}
+
+impl ComposedConverter {
+    fn identity() -> ComposedConverter {
+        return ComposedConverter {
+            pieces: vec![ComposedPiece {
+                start: 0,
+                end: u64::MAX,
+                offset: 0,
+            }],
+        };
+    }
+
+    fn then(&self, converter: &AlmanacConverter) -> ComposedConverter {
+        let next_pieces: Vec<ComposedPiece> = converter_pieces(converter);
+
+        let mut pieces: Vec<ComposedPiece> = Vec::new();
+        for piece in self.pieces.iter() {
+            let image_start: u64 = piece.apply(piece.start);
+            let image_end: u64 = piece.apply(piece.end);
+            for next_piece in next_pieces.iter() {
+                let overlap_start: u64 = u64::max(image_start, next_piece.start);
+                let overlap_end: u64 = u64::min(image_end, next_piece.end);
+                if overlap_start >= overlap_end {
+                    continue;
+                }
+
+                pieces.push(ComposedPiece {
+                    start: ((overlap_start as i128) - piece.offset) as u64,
+                    end: ((overlap_end as i128) - piece.offset) as u64,
+                    offset: piece.offset + next_piece.offset,
+                });
+            }
+        }
+
+        pieces.sort_by_key(|p| p.start);
+
+        let mut merged: Vec<ComposedPiece> = Vec::new();
+        for piece in pieces {
+            if let Some(last) = merged.last_mut() {
+                if last.end == piece.start && last.offset == piece.offset {
+                    last.end = piece.end;
+                    continue;
+                }
+            }
+
+            merged.push(piece);
+        }
+
+        return ComposedConverter { pieces: merged };
+    }
+
+    pub fn compose(sections: &[&AlmanacSection]) -> ComposedConverter {
+        let mut composed: ComposedConverter = ComposedConverter::identity();
+        for section in sections.iter() {
+            composed = composed.then(&section.converter);
+        }
+
+        return composed;
+    }
+
+    pub fn get(&self, k: u64) -> u64 {
+        let piece_idx: usize = self.pieces.partition_point(|p| p.start <= k);
+        if piece_idx == 0 {
+            return k;
+        }
+
+        let piece: &ComposedPiece = &self.pieces[piece_idx - 1];
+        if k >= piece.end {
+            return k;
+        }
+
+        return piece.apply(k);
+    }
+}
+
+impl fmt::Display for ComposedConverter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for piece in self.pieces.iter() {
+            writeln!(f, "[{}, {}) -> {:+}", piece.start, piece.end, piece.offset)?;
+        }
+
+        return Ok(());
+    }
+}
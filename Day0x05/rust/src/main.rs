@@ -1,7 +1,14 @@
+mod bench;
+mod compose;
+mod validate;
+
 use lazy_static::lazy_static;
 use rayon::prelude::*;
 use regex::{Regex, Split};
-use std::fs;
+use std::{
+    collections::{HashMap, VecDeque},
+    env, fs,
+};
 
 fn read_file(filename: &str) -> String {
     let file_contents: String =
@@ -18,6 +25,48 @@ fn split_whitespace(s: &str) -> Split {
     return RE.split(s);
 }
 
+type Interval = (u64, u64);
+
+fn subtract_intervals(ranges: &[Interval], cuts: &[Interval]) -> Vec<Interval> {
+    let mut remaining: Vec<Interval> = ranges.to_vec();
+    for &(cut_start, cut_end) in cuts.iter() {
+        let mut next_remaining: Vec<Interval> = Vec::new();
+        for &(start, end) in remaining.iter() {
+            if cut_end <= start || end <= cut_start {
+                next_remaining.push((start, end));
+                continue;
+            }
+
+            if start < cut_start {
+                next_remaining.push((start, cut_start));
+            }
+
+            if cut_end < end {
+                next_remaining.push((cut_end, end));
+            }
+        }
+
+        remaining = next_remaining;
+    }
+
+    return remaining;
+}
+
+fn intersect_intervals(ranges: &[Interval], others: &[Interval]) -> Vec<Interval> {
+    let mut intersection: Vec<Interval> = Vec::new();
+    for &(start, end) in ranges.iter() {
+        for &(other_start, other_end) in others.iter() {
+            let overlap_start: u64 = u64::max(start, other_start);
+            let overlap_end: u64 = u64::min(end, other_end);
+            if overlap_start < overlap_end {
+                intersection.push((overlap_start, overlap_end));
+            }
+        }
+    }
+
+    return intersection;
+}
+
 struct AlmanacMap {
     dest_start: u64,
     src_start: u64,
@@ -32,15 +81,116 @@ impl AlmanacMap {
 
         return None;
     }
+
+    fn split_range(&self, range: Interval) -> (Option<Interval>, Vec<Interval>) {
+        let (start, end) = range;
+        let src_end: u64 = self.src_start + self.length;
+        let overlap_start: u64 = u64::max(start, self.src_start);
+        let overlap_end: u64 = u64::min(end, src_end);
+        if overlap_start >= overlap_end {
+            return (None, vec![range]);
+        }
+
+        let mut leftovers: Vec<Interval> = Vec::new();
+        if start < overlap_start {
+            leftovers.push((start, overlap_start));
+        }
+
+        if overlap_end < end {
+            leftovers.push((overlap_end, end));
+        }
+
+        let mapped: Interval = (
+            (self.dest_start + overlap_start) - self.src_start,
+            (self.dest_start + overlap_end) - self.src_start,
+        );
+
+        return (Some(mapped), leftovers);
+    }
+
+    fn get_inverse(&self, v: u64) -> Option<u64> {
+        if v >= self.dest_start && v < self.dest_start + self.length {
+            return Some((self.src_start + v) - self.dest_start);
+        }
+
+        return None;
+    }
+
+    fn inverse_range(&self, range: Interval) -> Option<Interval> {
+        let (start, end) = range;
+        let overlap_start: u64 = u64::max(start, self.dest_start);
+        let overlap_end: u64 = u64::min(end, self.dest_start + self.length);
+        if overlap_start >= overlap_end {
+            return None;
+        }
+
+        return Some((
+            (self.src_start + overlap_start) - self.dest_start,
+            (self.src_start + overlap_end) - self.dest_start,
+        ));
+    }
 }
 
+const MAX_LOOKUP_TABLE_LEN: u64 = 4096;
+
 struct AlmanacConverter {
     maps: Vec<AlmanacMap>,
+    max_src_ends: Vec<u64>,
+    lookup_table: Option<(u64, Vec<u64>)>,
 }
 
 impl AlmanacConverter {
+    fn new(mut maps: Vec<AlmanacMap>) -> AlmanacConverter {
+        maps.sort_by_key(|c| c.src_start);
+
+        let mut max_src_ends: Vec<u64> = Vec::new();
+        let mut max_src_end: u64 = 0;
+        for map in maps.iter() {
+            max_src_end = u64::max(max_src_end, map.src_start.saturating_add(map.length));
+            max_src_ends.push(max_src_end);
+        }
+
+        let mut converter = AlmanacConverter {
+            maps: maps,
+            max_src_ends: max_src_ends,
+            lookup_table: None,
+        };
+
+        if let (Some(first_map), Some(&src_end)) =
+            (converter.maps.first(), converter.max_src_ends.last())
+        {
+            let src_start: u64 = first_map.src_start;
+            if src_end - src_start <= MAX_LOOKUP_TABLE_LEN {
+                let table: Vec<u64> = (src_start..src_end).map(|k| converter.get(k)).collect();
+                converter.lookup_table = Some((src_start, table));
+            }
+        }
+
+        return converter;
+    }
+
     fn get(&self, k: u64) -> u64 {
-        for map in self.maps.iter().map(|x| x) {
+        if let Some((table_start, table)) = &self.lookup_table {
+            if k >= *table_start && k - table_start < table.len() as u64 {
+                return table[(k - table_start) as usize];
+            }
+
+            return k;
+        }
+
+        let n_candidates: usize = self.maps.partition_point(|m| m.src_start <= k);
+        let map_idx: usize = self.max_src_ends[..n_candidates].partition_point(|&end| end <= k);
+        if map_idx < n_candidates {
+            if let Some(v) = self.maps[map_idx].get(k) {
+                return v;
+            }
+        }
+
+        return k;
+    }
+
+    fn get_linear(&self, k: u64) -> u64 {
+        for map in self.maps.iter() {
             if let Some(v) = map.get(k) {
                 return v;
             }
@@ -48,11 +198,75 @@ impl AlmanacConverter {
 
         return k;
     }
+
+    fn get_ranges(&self, ranges: &[Interval]) -> Vec<Interval> {
+        let mut mapped: Vec<Interval> = Vec::new();
+        let mut pending: Vec<Interval> = ranges
+            .iter()
+            .copied()
+            .filter(|&(start, end)| start < end)
+            .collect();
+        for map in self.maps.iter() {
+            let mut unmapped: Vec<Interval> = Vec::new();
+            for &range in pending.iter() {
+                let (mapped_range, mut leftovers) = map.split_range(range);
+                if let Some(r) = mapped_range {
+                    mapped.push(r);
+                }
+                unmapped.append(&mut leftovers);
+            }
+
+            pending = unmapped;
+        }
+
+        mapped.append(&mut pending);
+
+        return mapped;
+    }
+
+    fn get_inverse(&self, v: u64) -> Vec<u64> {
+        let mut keys: Vec<u64> = Vec::new();
+        for map in self.maps.iter() {
+            if let Some(k) = map.get_inverse(v) {
+                if self.get(k) == v && !keys.contains(&k) {
+                    keys.push(k);
+                }
+            }
+        }
+
+        if self.get(v) == v && !keys.contains(&v) {
+            keys.push(v);
+        }
+
+        return keys;
+    }
+
+    fn get_inverse_ranges(&self, ranges: &[Interval]) -> Vec<Interval> {
+        let mut keys: Vec<Interval> = Vec::new();
+        let mut claimed: Vec<Interval> = Vec::new();
+        for map in self.maps.iter() {
+            for &range in ranges.iter() {
+                if let Some(key_range) = map.inverse_range(range) {
+                    keys.append(&mut subtract_intervals(&[key_range], &claimed));
+                }
+            }
+
+            claimed.push((map.src_start, map.src_start + map.length));
+        }
+
+        keys.append(&mut subtract_intervals(ranges, &claimed));
+
+        return keys;
+    }
 }
 
 fn parse_converter(map_str: &str) -> AlmanacConverter {
     let mut maps: Vec<AlmanacMap> = Vec::new();
     for map_line in map_str.split::<char>('\n') {
+        if map_line.trim().is_empty() {
+            continue;
+        }
+
         let mut nums: Vec<u64> = Vec::new();
         for num_str in split_whitespace(map_line) {
             if let Ok(n) = num_str.parse::<u64>() {
@@ -71,150 +285,419 @@ fn parse_converter(map_str: &str) -> AlmanacConverter {
         });
     }
 
-    maps.sort_by_key(|c| c.dest_start);
-    let converter = AlmanacConverter { maps: maps };
+    let converter = AlmanacConverter::new(maps);
 
     return converter;
 }
 
-fn parse_almanac(
-    almanac: &str,
-) -> (
-    Vec<u64>,
-    AlmanacConverter,
-    AlmanacConverter,
-    AlmanacConverter,
-    AlmanacConverter,
-    AlmanacConverter,
-    AlmanacConverter,
-    AlmanacConverter,
-) {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(
-            r"^\s*seeds:\s*(.+)\n+\s*seed\-to\-soil\s+map:\s*([\S\s]*)\s*soil\-to\-fertilizer\s+map:\s*([\S\s]*)\s*fertilizer\-to\-water\s+map:\s*([\S\s]*)\s*water\-to\-light\s+map:\s*([\S\s]*)\s*light\-to\-temperature\s+map:\s*([\S\s]*)\s*temperature\-to\-humidity\s+map:\s*([\S\s]*)\s*humidity\-to\-location\s+map:\s*([\S\s]*)\s*$"
-        ).expect("Regex should compile");
-    }
-
-    let caps = RE.captures(almanac).expect("Almanac should be parsed");
-
-    let mut seeds: Vec<u64> = Vec::new();
-    for seed_str in split_whitespace(
-        caps.get(1)
-            .expect("Should be able to capture group")
-            .as_str(),
-    ) {
-        if let Ok(n) = seed_str.parse::<u64>() {
-            seeds.push(n);
-        }
-    }
-
-    let seed2soil: AlmanacConverter = parse_converter(
-        caps.get(2)
-            .expect("Should be able to capture group")
-            .as_str()
-            .trim(),
-    );
-    let soil2fertilizer: AlmanacConverter = parse_converter(
-        caps.get(3)
-            .expect("Should be able to capture group")
-            .as_str()
-            .trim(),
-    );
-    let fertilizer2water: AlmanacConverter = parse_converter(
-        caps.get(4)
-            .expect("Should be able to capture group")
-            .as_str()
-            .trim(),
-    );
-    let water2light: AlmanacConverter = parse_converter(
-        caps.get(5)
-            .expect("Should be able to capture group")
-            .as_str()
-            .trim(),
-    );
-    let light2temperature: AlmanacConverter = parse_converter(
-        caps.get(6)
-            .expect("Should be able to capture group")
-            .as_str()
-            .trim(),
-    );
-    let temperature2humidity: AlmanacConverter = parse_converter(
-        caps.get(7)
-            .expect("Should be able to capture group")
-            .as_str()
-            .trim(),
-    );
-    let humidity2location: AlmanacConverter = parse_converter(
-        caps.get(8)
-            .expect("Should be able to capture group")
-            .as_str()
-            .trim(),
-    );
-
-    return (
-        seeds,
-        seed2soil,
-        soil2fertilizer,
-        fertilizer2water,
-        water2light,
-        light2temperature,
-        temperature2humidity,
-        humidity2location,
-    );
+struct AlmanacSection {
+    src: String,
+    dest: String,
+    converter: AlmanacConverter,
+}
+
+struct Almanac {
+    seeds: Vec<u64>,
+    sections: Vec<AlmanacSection>,
+}
+
+impl Almanac {
+    fn parse(almanac: &str) -> Almanac {
+        lazy_static! {
+            static ref RE_BLOCK_SEP: Regex = Regex::new(r"\n\s*\n").expect("Regex should compile");
+            static ref RE_SEEDS: Regex =
+                Regex::new(r"^\s*seeds:\s*([\s\d]*)$").expect("Regex should compile");
+            static ref RE_SECTION: Regex = Regex::new(r"^\s*(\S+)\-to\-(\S+)\s+map:\s*([\s\d]*)$")
+                .expect("Regex should compile");
+        }
+
+        let mut seeds: Vec<u64> = Vec::new();
+        let mut sections: Vec<AlmanacSection> = Vec::new();
+        for block in RE_BLOCK_SEP.split(almanac.trim()) {
+            if let Some(caps) = RE_SEEDS.captures(block) {
+                for seed_str in split_whitespace(
+                    caps.get(1)
+                        .expect("Should be able to capture group")
+                        .as_str(),
+                ) {
+                    if let Ok(n) = seed_str.parse::<u64>() {
+                        seeds.push(n);
+                    }
+                }
+
+                continue;
+            }
+
+            let caps = RE_SECTION
+                .captures(block)
+                .expect("Almanac section should be parsed");
+            sections.push(AlmanacSection {
+                src: caps
+                    .get(1)
+                    .expect("Should be able to capture group")
+                    .as_str()
+                    .to_string(),
+                dest: caps
+                    .get(2)
+                    .expect("Should be able to capture group")
+                    .as_str()
+                    .to_string(),
+                converter: parse_converter(
+                    caps.get(3)
+                        .expect("Should be able to capture group")
+                        .as_str()
+                        .trim(),
+                ),
+            });
+        }
+
+        let almanac = Almanac {
+            seeds: seeds,
+            sections: sections,
+        };
+
+        return almanac;
+    }
+
+    fn path(&self, src: &str, dest: &str) -> Option<Vec<&AlmanacSection>> {
+        let mut prev_sections: HashMap<&str, Option<usize>> = HashMap::from([(src, None)]);
+        let mut queue: VecDeque<&str> = VecDeque::from([src]);
+        while let Some(category) = queue.pop_front() {
+            if category == dest {
+                break;
+            }
+
+            for (i, section) in self.sections.iter().enumerate() {
+                if section.src == category && !prev_sections.contains_key(section.dest.as_str()) {
+                    prev_sections.insert(&section.dest, Some(i));
+                    queue.push_back(&section.dest);
+                }
+            }
+        }
+
+        if !prev_sections.contains_key(dest) {
+            return None;
+        }
+
+        let mut path: Vec<&AlmanacSection> = Vec::new();
+        let mut category: &str = dest;
+        while let Some(&Some(i)) = prev_sections.get(category) {
+            let section: &AlmanacSection = &self.sections[i];
+            path.push(section);
+            category = &section.src;
+        }
+
+        path.reverse();
+
+        return Some(path);
+    }
+
+    fn convert(&self, src: &str, dest: &str, k: u64) -> Option<u64> {
+        let mut v: u64 = k;
+        for section in self.path(src, dest)? {
+            v = section.converter.get(v);
+        }
+
+        return Some(v);
+    }
+
+    fn convert_ranges(&self, src: &str, dest: &str, ranges: &[Interval]) -> Option<Vec<Interval>> {
+        let mut converted: Vec<Interval> = ranges.to_vec();
+        for section in self.path(src, dest)? {
+            converted = section.converter.get_ranges(&converted);
+        }
+
+        return Some(converted);
+    }
+
+    fn inverse_convert(&self, src: &str, dest: &str, v: u64) -> Option<Vec<u64>> {
+        let mut keys: Vec<u64> = vec![v];
+        for section in self.path(src, dest)?.iter().rev() {
+            let mut prev_keys: Vec<u64> = Vec::new();
+            for &k in keys.iter() {
+                for prev_k in section.converter.get_inverse(k) {
+                    if !prev_keys.contains(&prev_k) {
+                        prev_keys.push(prev_k);
+                    }
+                }
+            }
+
+            keys = prev_keys;
+        }
+
+        return Some(keys);
+    }
+
+    fn inverse_convert_ranges(
+        &self,
+        src: &str,
+        dest: &str,
+        ranges: &[Interval],
+    ) -> Option<Vec<Interval>> {
+        let mut converted: Vec<Interval> = ranges.to_vec();
+        for section in self.path(src, dest)?.iter().rev() {
+            converted = section.converter.get_inverse_ranges(&converted);
+        }
+
+        return Some(converted);
+    }
+
+    fn compose(&self, src: &str, dest: &str) -> Option<compose::ComposedConverter> {
+        let path: Vec<&AlmanacSection> = self.path(src, dest)?;
+
+        return Some(compose::ComposedConverter::compose(&path));
+    }
+
+    fn lowest_location_brute_force(&self, seed_ranges: &[Interval]) -> Option<u64> {
+        let path: Vec<&AlmanacSection> = self.path("seed", "location")?;
+        let lowests_in_range = seed_ranges
+            .par_iter()
+            .map(|&(start, end)| {
+                let mut lowest_in_range = u64::MAX;
+                for seed in start..end {
+                    let mut location: u64 = seed;
+                    for section in path.iter() {
+                        location = section.converter.get(location);
+                    }
+
+                    lowest_in_range = u64::min(lowest_in_range, location);
+                }
+
+                return lowest_in_range;
+            })
+            .collect::<Vec<u64>>();
+
+        let mut lowest_location: Option<u64> = None;
+        for (&(start, end), l) in seed_ranges.iter().zip(lowests_in_range) {
+            if start < end {
+                lowest_location = Some(lowest_location.map_or(l, |lowest| u64::min(lowest, l)));
+            }
+        }
+
+        return lowest_location;
+    }
+
+    fn lowest_location_by_inverse(&self, seed_ranges: &[Interval]) -> Option<u64> {
+        let mut low: u64 = 0;
+        let mut block_size: u64 = 1;
+        loop {
+            let high: u64 = low.saturating_add(block_size);
+            let candidate_seeds: Vec<Interval> = intersect_intervals(
+                &self.inverse_convert_ranges("seed", "location", &[(low, high)])?,
+                seed_ranges,
+            );
+            if !candidate_seeds.is_empty() {
+                let locations: Vec<Interval> =
+                    self.convert_ranges("seed", "location", &candidate_seeds)?;
+                let lowest_location: Option<u64> = intersect_intervals(&locations, &[(low, high)])
+                    .iter()
+                    .map(|&(start, _)| start)
+                    .min();
+
+                return lowest_location;
+            }
+
+            if high == u64::MAX {
+                return None;
+            }
+
+            low = high;
+            block_size = block_size.saturating_mul(2);
+        }
+    }
 }
 
 fn main() {
     let filename: &str = "../almanac.txt";
     let file_contents: String = read_file(filename);
-    let (
-        seeds,
-        seed2soil,
-        soil2fertilizer,
-        fertilizer2water,
-        water2light,
-        light2temperature,
-        temperature2humidity,
-        humidity2location,
-    ) = parse_almanac(&file_contents);
+    let almanac: Almanac = Almanac::parse(&file_contents);
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--bench") {
+        bench::run_benchmark();
+
+        return;
+    }
+
+    if !args.is_empty() && args[0] == "--convert" {
+        if args.len() != 4 {
+            panic!("Convert option should have source, destination and value arguments");
+        }
+
+        let k: u64 = args[3]
+            .parse::<u64>()
+            .expect("Value should be non-negative integer");
+        match almanac.convert(&args[1], &args[2], k) {
+            Some(v) => println!("{}", v),
+            None => println!("{} is not reachable from {}", args[2], args[1]),
+        }
+
+        return;
+    }
+
+    if args.iter().any(|arg| arg == "--validate") {
+        let issues: Vec<validate::SectionIssue> = validate::validate(&almanac.sections);
+        for issue in issues.iter() {
+            println!("{}", issue);
+        }
+
+        if issues.is_empty() {
+            println!("No issues found");
+        }
+
+        return;
+    }
+
+    if !args.is_empty() && args[0] == "--inverse" {
+        if args.len() != 4 {
+            panic!("Inverse option should have source, destination and value arguments");
+        }
+
+        let v: u64 = args[3]
+            .parse::<u64>()
+            .expect("Value should be non-negative integer");
+        match almanac.inverse_convert(&args[1], &args[2], v) {
+            Some(keys) => {
+                for k in keys {
+                    println!("{}", k);
+                }
+            }
+            None => println!("{} is not reachable from {}", args[2], args[1]),
+        }
+
+        return;
+    }
+
+    let seed2location: compose::ComposedConverter = almanac
+        .compose("seed", "location")
+        .expect("Should be able to convert seed to location");
+    if args.iter().any(|arg| arg == "--composed") {
+        print!("{}", seed2location);
+
+        return;
+    }
 
     let mut lowest_location = u64::MAX;
-    for &seed in seeds.iter().map(|x| x) {
-        let soil = seed2soil.get(seed);
-        let fertilizer = soil2fertilizer.get(soil);
-        let water = fertilizer2water.get(fertilizer);
-        let light = water2light.get(water);
-        let temperature = light2temperature.get(light);
-        let humidity = temperature2humidity.get(temperature);
-        let location = humidity2location.get(humidity);
+    for &seed in almanac.seeds.iter() {
+        let location: u64 = seed2location.get(seed);
         lowest_location = u64::min(lowest_location, location);
     }
 
     println!("{:?}", lowest_location);
 
-    let lowests_in_range = seeds
-        .par_chunks(2)
-        .map(|seed_pair| {
-            let mut lowest_in_range = u64::MAX;
-            for seed in seed_pair[0]..(seed_pair[0] + seed_pair[1]) {
-                let soil = seed2soil.get(seed);
-                let fertilizer = soil2fertilizer.get(soil);
-                let water = fertilizer2water.get(fertilizer);
-                let light = water2light.get(water);
-                let temperature = light2temperature.get(light);
-                let humidity = temperature2humidity.get(temperature);
-                let location = humidity2location.get(humidity);
+    let seed_ranges: Vec<Interval> = almanac
+        .seeds
+        .chunks(2)
+        .map(|seed_pair| (seed_pair[0], seed_pair[0] + seed_pair[1]))
+        .collect();
+    if args.iter().any(|arg| arg == "--brute-force") {
+        let lowest_location: u64 = almanac
+            .lowest_location_brute_force(&seed_ranges)
+            .expect("Some seed should have a location");
+        println!("{:?}", lowest_location);
 
-                lowest_in_range = u64::min(lowest_in_range, location);
-            }
+        return;
+    }
+    if args.iter().any(|arg| arg == "--search-locations") {
+        let lowest_location: u64 = almanac
+            .lowest_location_by_inverse(&seed_ranges)
+            .expect("Some seed should have a location");
+        println!("{:?}", lowest_location);
 
-            return lowest_in_range;
-        })
-        .collect::<Vec<u64>>();
+        return;
+    }
+
+    let location_ranges: Vec<Interval> = almanac
+        .convert_ranges("seed", "location", &seed_ranges)
+        .expect("Should be able to convert seed to location");
 
     lowest_location = u64::MAX;
-    for l in lowests_in_range {
-        lowest_location = u64::min(lowest_location, l);
+    for (start, _) in location_ranges {
+        lowest_location = u64::min(lowest_location, start);
     }
 
     println!("{:?}", lowest_location);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_ALMANAC: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    fn lowest_location_by_ranges(almanac: &Almanac, seed_ranges: &[Interval]) -> Option<u64> {
+        return almanac
+            .convert_ranges("seed", "location", seed_ranges)?
+            .iter()
+            .map(|&(start, _)| start)
+            .min();
+    }
+
+    #[test]
+    fn ranges_match_brute_force_on_sample() {
+        let almanac: Almanac = Almanac::parse(SAMPLE_ALMANAC);
+        let seed_ranges: [Interval; 2] = [(79, 93), (55, 68)];
+        assert_eq!(lowest_location_by_ranges(&almanac, &seed_ranges), Some(46));
+        assert_eq!(
+            lowest_location_by_ranges(&almanac, &seed_ranges),
+            almanac.lowest_location_brute_force(&seed_ranges)
+        );
+    }
+
+    #[test]
+    fn ranges_match_brute_force_with_empty_range() {
+        let almanac: Almanac = Almanac::parse(SAMPLE_ALMANAC);
+        let seed_ranges: [Interval; 2] = [(79, 93), (3, 3)];
+        assert_eq!(lowest_location_by_ranges(&almanac, &seed_ranges), Some(46));
+        assert_eq!(
+            lowest_location_by_ranges(&almanac, &seed_ranges),
+            almanac.lowest_location_brute_force(&seed_ranges)
+        );
+        assert_eq!(lowest_location_by_ranges(&almanac, &[(3, 3)]), None);
+        assert_eq!(almanac.lowest_location_brute_force(&[(3, 3)]), None);
+    }
+
+    #[test]
+    fn ranges_match_brute_force_per_seed() {
+        let almanac: Almanac = Almanac::parse(SAMPLE_ALMANAC);
+        for seed in 0..110 {
+            let seed_ranges: [Interval; 1] = [(seed, seed + 1)];
+            assert_eq!(
+                lowest_location_by_ranges(&almanac, &seed_ranges),
+                almanac.lowest_location_brute_force(&seed_ranges)
+            );
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{AlmanacMap, AlmanacSection};
+use std::fmt;
+
+pub enum MapIssue {
+    OverlappingSources { first: usize, second: usize },
+    OverlappingDestinations { first: usize, second: usize },
+    SourceOverflow { map: usize },
+    DestinationOverflow { map: usize },
+    CoverageGap { start: u64, end: u64 },
+}
+
+pub struct SectionIssue<'a> {
+    section: &'a AlmanacSection,
+    issue: MapIssue,
+}
+
+impl fmt::Display for SectionIssue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let maps: &Vec<AlmanacMap> = &self.section.converter.maps;
+        let map_str = |i: &usize| -> String {
+            return format!(
+                "{} {} {}",
+                maps[*i].dest_start, maps[*i].src_start, maps[*i].length
+            );
+        };
+
+        write!(f, "{}-to-{}: ", self.section.src, self.section.dest)?;
+        return match &self.issue {
+            MapIssue::OverlappingSources { first, second } => write!(
+                f,
+                "source ranges of '{}' and '{}' overlap",
+                map_str(first),
+                map_str(second)
+            ),
+            MapIssue::OverlappingDestinations { first, second } => write!(
+                f,
+                "destination ranges of '{}' and '{}' overlap",
+                map_str(first),
+                map_str(second)
+            ),
+            MapIssue::SourceOverflow { map } => {
+                write!(f, "source range of '{}' overflows", map_str(map))
+            }
+            MapIssue::DestinationOverflow { map } => {
+                write!(f, "destination range of '{}' overflows", map_str(map))
+            }
+            MapIssue::CoverageGap { start, end } => {
+                write!(f, "no source range covers [{}, {})", start, end)
+            }
+        };
+    }
+}
+
+fn overlaps(start: u64, length: u64, other_start: u64, other_length: u64) -> bool {
+    let end: u64 = start.saturating_add(length);
+    let other_end: u64 = other_start.saturating_add(other_length);
+
+    return length > 0 && other_length > 0 && start < other_end && other_start < end;
+}
+
+fn validate_section(section: &AlmanacSection) -> Vec<MapIssue> {
+    let maps: &Vec<AlmanacMap> = &section.converter.maps;
+    let mut issues: Vec<MapIssue> = Vec::new();
+
+    for (i, map) in maps.iter().enumerate() {
+        if map.src_start.checked_add(map.length).is_none() {
+            issues.push(MapIssue::SourceOverflow { map: i });
+        }
+
+        if map.dest_start.checked_add(map.length).is_none() {
+            issues.push(MapIssue::DestinationOverflow { map: i });
+        }
+    }
+
+    for i in 0..maps.len() {
+        for j in (i + 1)..maps.len() {
+            if overlaps(
+                maps[i].src_start,
+                maps[i].length,
+                maps[j].src_start,
+                maps[j].length,
+            ) {
+                issues.push(MapIssue::OverlappingSources {
+                    first: i,
+                    second: j,
+                });
+            }
+
+            if overlaps(
+                maps[i].dest_start,
+                maps[i].length,
+                maps[j].dest_start,
+                maps[j].length,
+            ) {
+                issues.push(MapIssue::OverlappingDestinations {
+                    first: i,
+                    second: j,
+                });
+            }
+        }
+    }
+
+    let mut src_ranges: Vec<(u64, u64)> = maps
+        .iter()
+        .filter(|m| m.length > 0)
+        .map(|m| (m.src_start, m.src_start.saturating_add(m.length)))
+        .collect();
+    src_ranges.sort();
+    let mut covered_end: Option<u64> = None;
+    for (start, end) in src_ranges {
+        if let Some(prev_end) = covered_end {
+            if start > prev_end {
+                issues.push(MapIssue::CoverageGap {
+                    start: prev_end,
+                    end: start,
+                });
+            }
+        }
+
+        covered_end = Some(u64::max(covered_end.unwrap_or(end), end));
+    }
+
+    return issues;
+}
+
+pub fn validate(sections: &[AlmanacSection]) -> Vec<SectionIssue<'_>> {
+    let mut issues: Vec<SectionIssue> = Vec::new();
+    for section in sections.iter() {
+        for issue in validate_section(section) {
+            issues.push(SectionIssue {
+                section: section,
+                issue: issue,
+            });
+        }
+    }
+
+    return issues;
+}
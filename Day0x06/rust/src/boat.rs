@@ -0,0 +1,120 @@
+use crate::Race;
+
+pub struct BoatModel {
+    pub acceleration: u64,
+    pub max_speed: Option<u64>,
+    pub startup_delay: u64,
+}
+
+pub struct RaceStrategy {
+    pub num_solutions: u64,
+    pub winning_holds: Option<(u64, u64)>,
+    pub optimal_hold: u64,
+    pub optimal_distance: u128,
+    pub margin: i128,
+}
+
+impl BoatModel {
+    pub fn new() -> BoatModel {
+        return BoatModel {
+            acceleration: 1,
+            max_speed: None,
+            startup_delay: 0,
+        };
+    }
+
+    // The speed always fits in a u128, but the distance can exceed it for
+    // large accelerations, so it saturates at u128::MAX, which still beats
+    // any u64 record.
+    pub fn distance(&self, time: u64, hold: u64) -> u128 {
+        let moving_time: u64 = time.saturating_sub(hold).saturating_sub(self.startup_delay);
+        let mut speed: u128 = (self.acceleration as u128) * (hold as u128);
+        if let Some(max_speed) = self.max_speed {
+            speed = u128::min(speed, max_speed as u128);
+        }
+
+        return speed.saturating_mul(moving_time as u128);
+    }
+
+    // Distance rises with the hold time until the boat either reaches its top
+    // speed or the peak of `hold * (time - delay - hold)`, and never rises
+    // again afterwards, so the optimal hold is one of a few candidates and the
+    // winning holds form a single interval around it.
+    fn optimal_hold(&self, time: u64) -> u64 {
+        let moving_time: u64 = time.saturating_sub(self.startup_delay);
+        let mut candidates: Vec<u64> = vec![0, moving_time / 2, moving_time.div_ceil(2)];
+        if let Some(max_speed) = self.max_speed {
+            if self.acceleration > 0 {
+                let capped_hold: u64 = max_speed.div_ceil(self.acceleration);
+                candidates.push(capped_hold.saturating_sub(1));
+                candidates.push(capped_hold);
+            }
+        }
+
+        let mut optimal_hold: u64 = 0;
+        let mut optimal_distance: u128 = 0;
+        for hold in candidates {
+            let hold: u64 = u64::min(hold, time);
+            let distance: u128 = self.distance(time, hold);
+            if distance > optimal_distance || (distance == optimal_distance && hold < optimal_hold)
+            {
+                optimal_hold = hold;
+                optimal_distance = distance;
+            }
+        }
+
+        return optimal_hold;
+    }
+
+    pub fn strategy(&self, race: &Race) -> RaceStrategy {
+        let optimal_hold: u64 = self.optimal_hold(race.time);
+        let optimal_distance: u128 = self.distance(race.time, optimal_hold);
+        let record: u128 = race.distance as u128;
+        let margin: i128 = if optimal_distance >= record {
+            i128::try_from(optimal_distance - record).unwrap_or(i128::MAX)
+        } else {
+            -((record - optimal_distance) as i128)
+        };
+        if optimal_distance <= record {
+            return RaceStrategy {
+                num_solutions: 0,
+                winning_holds: None,
+                optimal_hold: optimal_hold,
+                optimal_distance: optimal_distance,
+                margin: margin,
+            };
+        }
+
+        let mut low: u64 = 0;
+        let mut high: u64 = optimal_hold;
+        while low < high {
+            let mid: u64 = low + (high - low) / 2;
+            if self.distance(race.time, mid) > record {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        let lowest_hold: u64 = low;
+
+        low = optimal_hold;
+        high = race.time;
+        while low < high {
+            let mid: u64 = low + (high - low).div_ceil(2);
+            if self.distance(race.time, mid) > record {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        let highest_hold: u64 = low;
+
+        return RaceStrategy {
+            num_solutions: highest_hold - lowest_hold + 1,
+            winning_holds: Some((lowest_hold, highest_hold)),
+            optimal_hold: optimal_hold,
+            optimal_distance: optimal_distance,
+            margin: margin,
+        };
+    }
+}
@@ -1,6 +1,9 @@
-use std::{fs, iter::zip};
+mod boat;
+
+use std::{env, fs, iter::zip};
 
 use lazy_static::lazy_static;
+use num_bigint::BigUint;
 use regex::Regex;
 
 fn read_file(filename: &str) -> String {
@@ -10,25 +13,17 @@ fn read_file(filename: &str) -> String {
     return file_contents;
 }
 
-fn quadratic(a: i64, b: i64, c: i64) -> Vec<f64> {
-    let mut solutions: Vec<f64> = Vec::new();
-    let discriminant: i64 = i64::pow(b, 2) - (4 * a * c);
-    if discriminant < 0 {
-        return solutions;
+fn isqrt(n: u128) -> u128 {
+    let mut root: u128 = f64::sqrt(n as f64) as u128;
+    while root.checked_mul(root).is_none_or(|sq| sq > n) {
+        root -= 1;
     }
 
-    let minus_b: f64 = -(b as f64);
-    let two_a: f64 = (2 * a) as f64;
-    let sqrt_discriminant: f64 = f64::sqrt(discriminant as f64);
-
-    solutions.push((minus_b + sqrt_discriminant) / two_a);
-    if discriminant == 0 {
-        return solutions;
+    while (root + 1).checked_mul(root + 1).is_some_and(|sq| sq <= n) {
+        root += 1;
     }
 
-    solutions.push((minus_b - sqrt_discriminant) / two_a);
-
-    return solutions;
+    return root;
 }
 
 struct Race {
@@ -73,14 +68,19 @@ fn parse_races(s: &str) -> Vec<Race> {
     return races;
 }
 
-fn parse_race(s: &str) -> Race {
+struct BigRace {
+    time: BigUint,
+    distance: BigUint,
+}
+
+fn parse_race(s: &str) -> BigRace {
     lazy_static! {
         static ref RE_TIME: Regex = Regex::new(r"Time:([\s\d]+)").expect("Regex should compile");
         static ref RE_DISTANCE: Regex =
             Regex::new(r"Distance:([\s\d]+)").expect("Regex should compile");
     }
 
-    let time: u64 = RE_TIME
+    let time: BigUint = RE_TIME
         .captures(s)
         .expect("Should be able to capture groups")
         .get(1)
@@ -88,10 +88,10 @@ fn parse_race(s: &str) -> Race {
         .as_str()
         .split_whitespace()
         .fold("".to_string(), |acc: String, t: &str| acc + t)
-        .parse::<u64>()
+        .parse::<BigUint>()
         .expect("Time should be parsed");
 
-    let distance: u64 = RE_DISTANCE
+    let distance: BigUint = RE_DISTANCE
         .captures(s)
         .expect("Should be able to capture groups")
         .get(1)
@@ -99,10 +99,10 @@ fn parse_race(s: &str) -> Race {
         .as_str()
         .split_whitespace()
         .fold("".to_string(), |acc: String, d: &str| acc + d)
-        .parse::<u64>()
+        .parse::<BigUint>()
         .expect("Time should be parsed");
 
-    let race: Race = Race {
+    let race: BigRace = BigRace {
         time: time,
         distance: distance,
     };
@@ -110,35 +110,68 @@ fn parse_race(s: &str) -> Race {
     return race;
 }
 
-fn round_next_integer(f: f64, up: bool) -> i64 {
-    if f.fract() == 0.0 {
-        let i = f as i64;
-        if up {
-            return i + 1;
-        }
+fn beats_record(race: &Race, hold: u64) -> bool {
+    let distance: u128 = (hold as u128) * ((race.time - hold) as u128);
 
-        return i - 1;
+    return distance > race.distance as u128;
+}
+
+fn compute_num_solutions(race: &Race) -> u64 {
+    let time: u128 = race.time as u128;
+    let time_squared: u128 = time * time;
+    let four_distance: u128 = 4 * (race.distance as u128);
+    if time_squared <= four_distance {
+        return 0;
+    }
+
+    let discriminant: u128 = time_squared - four_distance;
+    let sqrt_discriminant: u128 = isqrt(discriminant);
+    let mut lowest_hold: u64 = ((time - u128::min(sqrt_discriminant, time)) / 2) as u64;
+    while lowest_hold <= race.time / 2 && !beats_record(race, lowest_hold) {
+        lowest_hold += 1;
     }
 
-    if up {
-        return f.ceil() as i64;
+    while lowest_hold > 0 && beats_record(race, lowest_hold - 1) {
+        lowest_hold -= 1;
     }
 
-    return f.floor() as i64;
+    if lowest_hold > race.time / 2 {
+        return 0;
+    }
+
+    let num_solutions: u64 = race.time - (2 * lowest_hold) + 1;
+
+    return num_solutions;
+}
+
+fn beats_record_big(race: &BigRace, hold: &BigUint) -> bool {
+    return hold * (&race.time - hold) > race.distance;
 }
 
-fn compute_num_solutions(race: Race) -> i64 {
-    let solutions = quadratic(1, -(race.time as i64), race.distance as i64);
-    if solutions.len() != 2 {
-        panic!("Expected 2 solutions");
+fn compute_num_solutions_big(race: &BigRace) -> BigUint {
+    let time_squared: BigUint = &race.time * &race.time;
+    let four_distance: BigUint = &race.distance * 4u32;
+    if time_squared <= four_distance {
+        return BigUint::ZERO;
+    }
+
+    let sqrt_discriminant: BigUint = (time_squared - four_distance).sqrt();
+    let half_time: BigUint = &race.time / 2u32;
+    let mut lowest_hold: BigUint =
+        (&race.time - BigUint::min(sqrt_discriminant, race.time.clone())) / 2u32;
+    while lowest_hold <= half_time && !beats_record_big(race, &lowest_hold) {
+        lowest_hold += 1u32;
+    }
+
+    while lowest_hold > BigUint::ZERO && beats_record_big(race, &(&lowest_hold - 1u32)) {
+        lowest_hold -= 1u32;
     }
 
-    let lowest_solution: f64 = solutions.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-    let highest_solution: f64 = solutions.iter().fold(0 as f64, |a, &b| a.max(b));
-    let lowest_int_solution: i64 = round_next_integer(lowest_solution, true);
-    let highest_int_solution: i64 = round_next_integer(highest_solution, false);
+    if lowest_hold > half_time {
+        return BigUint::ZERO;
+    }
 
-    let num_solutions: i64 = highest_int_solution - lowest_int_solution + 1;
+    let num_solutions: BigUint = &race.time - (lowest_hold * 2u32) + 1u32;
 
     return num_solutions;
 }
@@ -146,16 +179,172 @@ fn compute_num_solutions(race: Race) -> i64 {
 fn main() {
     let filename: &str = "../races.txt";
     let file_contents: String = read_file(filename);
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let mut model: boat::BoatModel = boat::BoatModel::new();
+    let mut custom_model: bool = false;
+    for (i, arg) in args.iter().enumerate() {
+        let parse_arg = || -> u64 {
+            return args
+                .get(i + 1)
+                .expect("Model option should have a value")
+                .parse::<u64>()
+                .expect("Model option should be non-negative integer");
+        };
+
+        match arg.as_str() {
+            "--acceleration" => model.acceleration = parse_arg(),
+            "--max-speed" => model.max_speed = Some(parse_arg()),
+            "--delay" => model.startup_delay = parse_arg(),
+            _ => continue,
+        }
+        custom_model = true;
+    }
+
+    if args.iter().any(|arg| arg == "--csv") {
+        println!("race,time,record,hold,distance,wins");
+        let races: Vec<Race> = parse_races(&file_contents);
+        for (i, race) in races.iter().enumerate() {
+            for hold in 0..=race.time {
+                let distance: u128 = model.distance(race.time, hold);
+                println!(
+                    "{},{},{},{},{},{}",
+                    i + 1,
+                    race.time,
+                    race.distance,
+                    hold,
+                    distance,
+                    distance > race.distance as u128
+                );
+            }
+        }
+
+        return;
+    }
+
+    if custom_model || args.iter().any(|arg| arg == "--report") {
+        println!(
+            "race\ttime\trecord\tways\tmin hold\tmax hold\toptimal hold\tbest distance\tmargin"
+        );
+        let races: Vec<Race> = parse_races(&file_contents);
+        for (i, race) in races.iter().enumerate() {
+            let strategy: boat::RaceStrategy = model.strategy(race);
+            let (min_hold_str, max_hold_str) = match strategy.winning_holds {
+                Some((min_hold, max_hold)) => (min_hold.to_string(), max_hold.to_string()),
+                None => (String::from("-"), String::from("-")),
+            };
+            println!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                i + 1,
+                race.time,
+                race.distance,
+                strategy.num_solutions,
+                min_hold_str,
+                max_hold_str,
+                strategy.optimal_hold,
+                strategy.optimal_distance,
+                strategy.margin
+            );
+        }
+
+        return;
+    }
+
     let races: Vec<Race> = parse_races(&file_contents);
-    let mut prod_solutions: i64 = 1;
-    for race in races {
+    let mut prod_solutions: u64 = 1;
+    for race in races.iter() {
         prod_solutions *= compute_num_solutions(race);
     }
 
     println!("{:?}", prod_solutions);
 
-    let race = parse_race(&file_contents);
-    let num_solutions: i64 = compute_num_solutions(race);
+    let race: BigRace = parse_race(&file_contents);
+    let num_solutions: BigUint = compute_num_solutions_big(&race);
 
     println!("{:?}", num_solutions);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_num_solutions(time: u64, distance: u64, expected: u64) {
+        let race = Race {
+            time: time,
+            distance: distance,
+        };
+        let big_race = BigRace {
+            time: BigUint::from(time),
+            distance: BigUint::from(distance),
+        };
+        assert_eq!(compute_num_solutions(&race), expected);
+        assert_eq!(
+            boat::BoatModel::new().strategy(&race).num_solutions,
+            expected
+        );
+        assert_eq!(
+            compute_num_solutions_big(&big_race),
+            BigUint::from(expected)
+        );
+    }
+
+    #[test]
+    fn isqrt_near_perfect_squares() {
+        for k in [
+            (1u128 << 60) - 1,
+            1 << 60,
+            (1 << 60) + 1,
+            (1 << 60) + 12345,
+            (1 << 63) + 1,
+            u64::MAX as u128,
+        ] {
+            assert_eq!(isqrt((k * k) - 1), k - 1);
+            assert_eq!(isqrt(k * k), k);
+            assert_eq!(isqrt((k * k) + 1), k);
+        }
+
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn num_solutions_matches_brute_force() {
+        for time in 0..=200 {
+            let half_square: u64 = (time / 2) * (time - (time / 2));
+            for distance in half_square.saturating_sub(3)..=(half_square + 1) {
+                let race = Race {
+                    time: time,
+                    distance: distance,
+                };
+                let expected: u64 = (0..=time).filter(|&h| beats_record(&race, h)).count() as u64;
+                assert_num_solutions(time, distance, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn num_solutions_near_large_perfect_squares() {
+        for k in [(1u128 << 60) - 1, 1 << 60, (1 << 60) + 1, (1 << 60) + 12345] {
+            for discriminant in [(k * k) - 1, k * k, (k * k) + 1] {
+                for time in (k + 1)..(k + 24) {
+                    let time_squared: u128 = time * time;
+                    if !(time_squared - discriminant).is_multiple_of(4) {
+                        continue;
+                    }
+
+                    let distance: u64 = u64::try_from((time_squared - discriminant) / 4)
+                        .expect("Distance should fit in a u64");
+                    let race = Race {
+                        time: time as u64,
+                        distance: distance,
+                    };
+                    let num_solutions: u64 = compute_num_solutions(&race);
+                    let lowest_hold: u64 = (race.time - num_solutions).div_ceil(2);
+                    assert!(num_solutions > 0);
+                    assert!(beats_record(&race, lowest_hold));
+                    assert!(!beats_record(&race, lowest_hold - 1));
+                    assert_num_solutions(race.time, distance, num_solutions);
+                }
+            }
+        }
+    }
+}